@@ -6,7 +6,7 @@ use criterion::{
     black_box, criterion_group, criterion_main, AxisScale, BenchmarkId, Criterion,
     PlotConfiguration,
 };
-use ibig::{modular::ModuloRing, ops::DivRem, ubig, UBig};
+use ibig::{modular::ModuloRing, ops::DivRem, ubig, Divisor, UBig};
 use rand::prelude::*;
 use std::fmt::Write;
 
@@ -69,6 +69,56 @@ fn bench_mul(criterion: &mut Criterion) {
     group.finish();
 }
 
+fn bench_mul_unbalanced(criterion: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut group = criterion.benchmark_group("mul_unbalanced");
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for log_bits in 3..=6 {
+        let bits = 10usize.pow(log_bits);
+        let a = random_ubig(2 * bits, &mut rng);
+        let b = random_ubig(bits, &mut rng);
+        group.bench_with_input(BenchmarkId::from_parameter(bits), &bits, |bencher, _| {
+            bencher.iter(|| black_box(&a) * black_box(&b))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_mul_very_unbalanced(criterion: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut group = criterion.benchmark_group("mul_very_unbalanced");
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for log_bits in 3..=5 {
+        let bits = 10usize.pow(log_bits);
+        let a = random_ubig(100 * bits, &mut rng);
+        let b = random_ubig(bits, &mut rng);
+        group.bench_with_input(BenchmarkId::from_parameter(bits), &bits, |bencher, _| {
+            bencher.iter(|| black_box(&a) * black_box(&b))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_square(criterion: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut group = criterion.benchmark_group("square");
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for log_bits in 1..=6 {
+        let bits = 10usize.pow(log_bits);
+        let a = random_ubig(bits, &mut rng);
+        group.bench_with_input(BenchmarkId::from_parameter(bits), &bits, |bencher, _| {
+            bencher.iter(|| black_box(&a).square())
+        });
+    }
+
+    group.finish();
+}
+
 fn bench_div(criterion: &mut Criterion) {
     let mut rng = StdRng::seed_from_u64(1);
     let mut group = criterion.benchmark_group("div");
@@ -86,12 +136,29 @@ fn bench_div(criterion: &mut Criterion) {
     group.finish();
 }
 
+fn bench_div_divisor(criterion: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut group = criterion.benchmark_group("div_divisor");
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for log_bits in 1..=6 {
+        let bits = 10usize.pow(log_bits);
+        let a = random_ubig(2 * bits, &mut rng);
+        let b = Divisor::new(&random_ubig(bits, &mut rng));
+        group.bench_with_input(BenchmarkId::from_parameter(bits), &bits, |bencher, _| {
+            bencher.iter(|| black_box(&b).div_rem(black_box(&a)))
+        });
+    }
+
+    group.finish();
+}
+
 fn bench_gcd(criterion: &mut Criterion) {
     let mut rng = StdRng::seed_from_u64(1);
     let mut group = criterion.benchmark_group("gcd");
     group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
 
-    for log_bits in 1..=5 {
+    for log_bits in 1..=6 {
         let bits = 10usize.pow(log_bits);
         let a = random_ubig(bits, &mut rng);
         let b = random_ubig(bits, &mut rng);
@@ -105,7 +172,7 @@ fn bench_gcd(criterion: &mut Criterion) {
     let mut group = criterion.benchmark_group("extended_gcd");
     group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
 
-    for log_bits in 1..=5 {
+    for log_bits in 1..=6 {
         let bits = 10usize.pow(log_bits);
         let a = random_ubig(bits, &mut rng);
         let b = random_ubig(bits, &mut rng);
@@ -207,6 +274,47 @@ fn bench_pow(criterion: &mut Criterion) {
     group.finish();
 }
 
+/// a * (a+1) * ... * (b-1), as in examples/factorial.rs.
+fn product(a: u32, b: u32) -> UBig {
+    if b == a + 1 {
+        UBig::from(a)
+    } else {
+        let mid = a + (b - a) / 2;
+        product(a, mid) * product(mid, b)
+    }
+}
+
+fn bench_factorial(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("factorial");
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for log_n in 1..=5 {
+        let n = 10u32.pow(log_n);
+        group.bench_with_input(BenchmarkId::new("product", n), &n, |bencher, n| {
+            bencher.iter(|| product(1, black_box(*n) + 1))
+        });
+        group.bench_with_input(BenchmarkId::new("factorial", n), &n, |bencher, n| {
+            bencher.iter(|| ibig::factorial(black_box(*n) as usize))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_fibonacci(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("fibonacci");
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for log_n in 1..=6 {
+        let n = 10usize.pow(log_n);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n, |bencher, n| {
+            bencher.iter(|| ibig::fibonacci(black_box(*n)))
+        });
+    }
+
+    group.finish();
+}
+
 fn bench_modulo_mul(criterion: &mut Criterion) {
     let mut rng = StdRng::seed_from_u64(1);
     let mut group = criterion.benchmark_group("modulo_mul");
@@ -252,13 +360,19 @@ criterion_group!(
     bench_add,
     bench_sub,
     bench_mul,
+    bench_mul_unbalanced,
+    bench_mul_very_unbalanced,
+    bench_square,
     bench_div,
+    bench_div_divisor,
     bench_gcd,
     bench_to_hex,
     bench_to_dec,
     bench_from_hex,
     bench_from_dec,
     bench_pow,
+    bench_factorial,
+    bench_fibonacci,
     bench_modulo_mul,
     bench_modulo_pow,
 );
@@ -0,0 +1,358 @@
+//! Tune algorithm thresholds for the host machine.
+//!
+//! Usage: `cargo run --release --bin tune_thresholds [-- <arch directory>]`
+//!
+//! The arch directory is a subdirectory of `src/arch`. By default it is chosen based on the
+//! host, e.g. `x86_64` or `generic_64_bit`. Architectures that have not been tuned use the shared
+//! defaults in `src/arch/generic/thresholds.rs`. Tuning them gives them their own
+//! `thresholds.rs`, and their `mod.rs` is switched to it.
+//!
+//! Thresholds are tuned one at a time, in order. The library is copied to a scratch directory
+//! under `target`. For each candidate value the scratch copy of `thresholds.rs` is rewritten,
+//! this binary is rebuilt against the modified copy, and run with `--measure` to time
+//! operations over a range of sizes around the candidates. The candidate with the smallest
+//! geometric mean time is kept. The real `thresholds.rs` is only written once all thresholds
+//! are tuned, so a failed or interrupted run leaves the source tree unchanged.
+
+use ibig::{modular::ModuloRing, UBig};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    env, fs,
+    hint::black_box,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+struct Threshold {
+    name: &'static str,
+    doc: &'static str,
+    candidates: &'static [usize],
+}
+
+const THRESHOLDS: [Threshold; 9] = [
+    Threshold {
+        name: "MUL_MAX_LEN_SIMPLE",
+        doc: "If the smaller factor is at most this long, use simple multiplication.",
+        candidates: &[8, 12, 16, 20, 24, 32, 40, 48, 64],
+    },
+    Threshold {
+        name: "MUL_MAX_LEN_KARATSUBA",
+        doc: "If the smaller factor is at most this long, use Karatsuba multiplication.",
+        candidates: &[64, 96, 128, 160, 192, 256, 320, 384],
+    },
+    Threshold {
+        name: "MUL_MAX_LEN_TOOM_3",
+        doc: "If the smaller factor is at most this long, use Toom-3 multiplication.",
+        candidates: &[150, 200, 300, 400, 600, 800, 1200],
+    },
+    Threshold {
+        name: "DIV_MAX_LEN_SIMPLE",
+        doc: "If the divisor or the quotient is at most this long, use simple division.",
+        candidates: &[8, 16, 24, 32, 48, 64, 96],
+    },
+    Threshold {
+        name: "DIV_MIN_LEN_BARRETT",
+        doc: "If the divisor is at least this long and the quotient much longer, divide by a reciprocal.",
+        candidates: &[250, 500, 1000, 2000, 4000],
+    },
+    Threshold {
+        name: "DIV_MIN_LEN_NEWTON",
+        doc: "If a reciprocal is at least this long, compute it by Newton's method.",
+        candidates: &[4000, 8000, 16000, 32000],
+    },
+    Threshold {
+        name: "REDC_MIN_LEN_MUL",
+        doc: "If the modulus is at least this long, Montgomery reduction uses multiplication.",
+        candidates: &[250, 500, 750, 1000, 1500, 2000],
+    },
+    Threshold {
+        name: "FMT_CHUNK_LEN",
+        doc: "Format in a non-power-of-two radix in chunks of this many words.",
+        candidates: &[4, 8, 16, 32, 64],
+    },
+    Threshold {
+        name: "PARSE_CHUNK_LEN",
+        doc: "Parse in a non-power-of-two radix in chunks of this many words.",
+        candidates: &[32, 64, 128, 256, 512, 1024],
+    },
+];
+
+/// Thresholds for architectures that have not been tuned.
+const DEFAULT_THRESHOLDS_PATH: &str = "src/arch/generic/thresholds.rs";
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("--measure") => {
+            let name = args.get(2).expect("missing threshold name");
+            println!("{}", measure(threshold(name)));
+        }
+        arch_dir => tune(arch_dir.unwrap_or_else(|| default_arch_dir())),
+    }
+}
+
+fn threshold(name: &str) -> &'static Threshold {
+    THRESHOLDS
+        .iter()
+        .find(|t| t.name == name)
+        .unwrap_or_else(|| panic!("unknown threshold {}", name))
+}
+
+fn default_arch_dir() -> &'static str {
+    if cfg!(target_arch = "x86_64") {
+        "x86_64"
+    } else if cfg!(target_arch = "x86") {
+        "x86"
+    } else if cfg!(target_pointer_width = "16") {
+        "generic_16_bit"
+    } else if cfg!(target_pointer_width = "32") {
+        "generic_32_bit"
+    } else {
+        "generic_64_bit"
+    }
+}
+
+fn root_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+fn scratch_dir() -> PathBuf {
+    root_dir().join("target/tune_thresholds")
+}
+
+fn tune(arch_dir: &str) {
+    let arch_path = Path::new("src/arch").join(arch_dir);
+    let thresholds_path = arch_path.join("thresholds.rs");
+    let path = root_dir().join(&thresholds_path);
+    let has_own_thresholds = path.exists();
+    let original_path = if has_own_thresholds {
+        path.clone()
+    } else {
+        root_dir().join(DEFAULT_THRESHOLDS_PATH)
+    };
+    let original = fs::read_to_string(&original_path)
+        .unwrap_or_else(|e| panic!("can't read {}: {}", original_path.display(), e));
+    let mut values: Vec<usize> = THRESHOLDS
+        .iter()
+        .map(|t| parse_value(&original, t.name))
+        .collect();
+
+    let scratch_root = scratch_dir().join("source");
+    copy_sources(&scratch_root);
+    if !has_own_thresholds {
+        use_own_thresholds(&scratch_root.join(&arch_path));
+    }
+    let scratch_path = scratch_root.join(&thresholds_path);
+
+    for (i, t) in THRESHOLDS.iter().enumerate() {
+        let mut best: Option<(f64, usize)> = None;
+        for &candidate in t.candidates {
+            values[i] = candidate;
+            fs::write(&scratch_path, generate(&values)).unwrap();
+            let score = run_measure(&scratch_root, t.name);
+            println!("{} = {}: {:.4}", t.name, candidate, score);
+            match best {
+                Some((best_score, _)) if best_score <= score => {}
+                _ => best = Some((score, candidate)),
+            }
+        }
+        values[i] = best.unwrap().1;
+        println!("{} = {}", t.name, values[i]);
+    }
+
+    if !has_own_thresholds {
+        use_own_thresholds(&root_dir().join(&arch_path));
+    }
+    fs::write(&path, generate(&values)).unwrap();
+    println!("Wrote {}", path.display());
+}
+
+/// Switch the architecture module in `arch_dir` from the shared default thresholds to its own
+/// `thresholds.rs`.
+fn use_own_thresholds(arch_dir: &Path) {
+    let mod_path = arch_dir.join("mod.rs");
+    let source = fs::read_to_string(&mod_path)
+        .unwrap_or_else(|e| panic!("can't read {}: {}", mod_path.display(), e));
+    let shared = "#[path = \"../generic/thresholds.rs\"]\npub(crate) mod thresholds;";
+    assert!(
+        source.contains(shared),
+        "{} doesn't use the default thresholds",
+        mod_path.display()
+    );
+    let source = source.replace(shared, "pub(crate) mod thresholds;");
+    fs::write(&mod_path, source).unwrap();
+}
+
+/// Copy the library and this tool to `dest`, replacing an earlier copy.
+fn copy_sources(dest: &Path) {
+    if dest.exists() {
+        fs::remove_dir_all(dest).unwrap();
+    }
+    let root = root_dir();
+    for entry in [
+        "Cargo.toml",
+        "Cargo.lock",
+        "src",
+        "benches",
+        "examples",
+        "tests",
+        "dev-tools/Cargo.toml",
+        "dev-tools/src",
+    ] {
+        let from = root.join(entry);
+        if from.exists() {
+            copy_recursive(&from, &dest.join(entry));
+        }
+    }
+}
+
+fn copy_recursive(from: &Path, to: &Path) {
+    if from.is_dir() {
+        fs::create_dir_all(to).unwrap();
+        for entry in fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            copy_recursive(&entry.path(), &to.join(entry.file_name()));
+        }
+    } else {
+        fs::create_dir_all(to.parent().unwrap()).unwrap();
+        fs::copy(from, to).unwrap();
+    }
+}
+
+/// Find `pub(crate) const NAME: usize = VALUE;` in a thresholds file.
+fn parse_value(source: &str, name: &str) -> usize {
+    let prefix = format!("pub(crate) const {}: usize = ", name);
+    source
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .and_then(|rest| rest.trim_end_matches(';').parse().ok())
+        .unwrap_or_else(|| panic!("{} not found", name))
+}
+
+fn generate(values: &[usize]) -> String {
+    let mut source = String::from(
+        "//! Algorithm thresholds, in words.\n\
+         //!\n\
+         //! Regenerate for the host with `cargo run --release --bin tune_thresholds` in `dev-tools`.\n",
+    );
+    for (t, value) in THRESHOLDS.iter().zip(values) {
+        source += &format!(
+            "\n/// {}\npub(crate) const {}: usize = {};\n",
+            t.doc, t.name, value
+        );
+    }
+    source
+}
+
+/// Rebuild the copy of the sources in `source_root` and measure.
+fn run_measure(source_root: &Path, name: &str) -> f64 {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .current_dir(source_root)
+        .args(["run", "--quiet", "--release", "--target-dir"])
+        .arg(scratch_dir().join("target"))
+        .args([
+            "-p",
+            "dev-tools",
+            "--bin",
+            "tune_thresholds",
+            "--",
+            "--measure",
+            name,
+        ])
+        .output()
+        .expect("failed to run cargo");
+    assert!(
+        output.status.success(),
+        "measurement failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap()
+}
+
+/// Sum of log times of the operation affected by the threshold over a range of sizes.
+fn measure(t: &Threshold) -> f64 {
+    let mut rng = StdRng::seed_from_u64(1);
+    let min_len = (t.candidates[0] / 2).max(2);
+    let max_len = t.candidates[t.candidates.len() - 1] * 2;
+    let mut score = 0.0;
+    let mut len = min_len;
+    while len <= max_len {
+        let time = match t.name {
+            "MUL_MAX_LEN_SIMPLE" | "MUL_MAX_LEN_KARATSUBA" | "MUL_MAX_LEN_TOOM_3" => {
+                let a = random_ubig(len, &mut rng);
+                let b = random_ubig(len, &mut rng);
+                time(|| black_box(&a) * black_box(&b))
+            }
+            "DIV_MAX_LEN_SIMPLE" => {
+                let a = random_ubig(2 * len, &mut rng);
+                let b = random_ubig(len, &mut rng);
+                time(|| black_box(&a) / black_box(&b))
+            }
+            "DIV_MIN_LEN_BARRETT" => {
+                let a = random_ubig(5 * len, &mut rng);
+                let b = random_ubig(len, &mut rng);
+                time(|| black_box(&a) / black_box(&b))
+            }
+            "DIV_MIN_LEN_NEWTON" => {
+                let a = random_ubig(len, &mut rng);
+                time(|| black_box(&a).reciprocal(2 * len * usize::BITS as usize))
+            }
+            "REDC_MIN_LEN_MUL" => {
+                let ring = ModuloRing::new(&(random_ubig(len, &mut rng) | UBig::from(1u8)));
+                let a = ring.from(random_ubig(len, &mut rng));
+                let b = ring.from(random_ubig(len, &mut rng));
+                time(|| black_box(&a) * black_box(&b))
+            }
+            "FMT_CHUNK_LEN" => {
+                let a = random_ubig(len, &mut rng);
+                time(|| black_box(&a).to_string())
+            }
+            "PARSE_CHUNK_LEN" => {
+                let s = random_ubig(len, &mut rng).to_string();
+                time(|| UBig::from_str_radix(black_box(&s), 10))
+            }
+            _ => unreachable!(),
+        };
+        score += time.as_secs_f64().ln();
+        len += (len / 4).max(1);
+    }
+    score
+}
+
+/// A random number of `len` words.
+fn random_ubig(len: usize, rng: &mut StdRng) -> UBig {
+    let bits = len * usize::BITS as usize;
+    rng.gen_range(UBig::from(1u8) << (bits - 1)..UBig::from(1u8) << bits)
+}
+
+/// The fastest time of a few runs of `f`.
+fn time<T, F: FnMut() -> T>(mut f: F) -> Duration {
+    // Choose the number of iterations so that a run takes at least 10 ms.
+    let mut iterations = 1;
+    loop {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            black_box(f());
+        }
+        if start.elapsed() >= Duration::from_millis(10) {
+            break;
+        }
+        iterations *= 2;
+    }
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..iterations {
+                black_box(f());
+            }
+            start.elapsed() / iterations
+        })
+        .min()
+        .unwrap()
+}
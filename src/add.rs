@@ -68,14 +68,7 @@ pub(crate) fn sub_word_in_place(words: &mut [Word], rhs: Word) -> bool {
 #[must_use]
 pub(crate) fn add_same_len_in_place(words: &mut [Word], rhs: &[Word]) -> bool {
     debug_assert!(words.len() == rhs.len());
-
-    let mut carry = false;
-    for (a, b) in words.iter_mut().zip(rhs.iter()) {
-        let (sum, c) = arch::add::add_with_carry(*a, *b, carry);
-        *a = sum;
-        carry = c;
-    }
-    carry
+    arch::add_chain::add_same_len_in_place(words, rhs)
 }
 
 /// lhs -= rhs
@@ -84,13 +77,7 @@ pub(crate) fn add_same_len_in_place(words: &mut [Word], rhs: &[Word]) -> bool {
 #[must_use]
 pub(crate) fn sub_same_len_in_place(lhs: &mut [Word], rhs: &[Word]) -> bool {
     debug_assert!(lhs.len() == rhs.len());
-    let mut borrow = false;
-    for (a, b) in lhs.iter_mut().zip(rhs.iter()) {
-        let (diff, borrow1) = arch::add::sub_with_borrow(*a, *b, borrow);
-        *a = diff;
-        borrow = borrow1;
-    }
-    borrow
+    arch::add_chain::sub_same_len_in_place(lhs, rhs)
 }
 
 /// lhs += rhs
@@ -0,0 +1,31 @@
+use crate::arch::{add, word::Word};
+
+/// Add a word sequence of same length in place.
+///
+/// Returns overflow.
+#[inline]
+pub(crate) fn add_same_len_in_place(words: &mut [Word], rhs: &[Word]) -> bool {
+    debug_assert!(words.len() == rhs.len());
+    let mut carry = false;
+    for (a, b) in words.iter_mut().zip(rhs.iter()) {
+        let (sum, c) = add::add_with_carry(*a, *b, carry);
+        *a = sum;
+        carry = c;
+    }
+    carry
+}
+
+/// lhs -= rhs
+///
+/// Returns borrow.
+#[inline]
+pub(crate) fn sub_same_len_in_place(lhs: &mut [Word], rhs: &[Word]) -> bool {
+    debug_assert!(lhs.len() == rhs.len());
+    let mut borrow = false;
+    for (a, b) in lhs.iter_mut().zip(rhs.iter()) {
+        let (diff, b1) = add::sub_with_borrow(*a, *b, borrow);
+        *a = diff;
+        borrow = b1;
+    }
+    borrow
+}
@@ -0,0 +1,31 @@
+//! Default algorithm thresholds, in words, for architectures that have not been tuned.
+//!
+//! `cargo run --release --bin tune_thresholds` in `dev-tools` gives the host architecture its
+//! own tuned `thresholds.rs` in place of this file.
+
+/// If the smaller factor is at most this long, use simple multiplication.
+pub(crate) const MUL_MAX_LEN_SIMPLE: usize = 24;
+
+/// If the smaller factor is at most this long, use Karatsuba multiplication.
+pub(crate) const MUL_MAX_LEN_KARATSUBA: usize = 192;
+
+/// If the smaller factor is at most this long, use Toom-3 multiplication.
+pub(crate) const MUL_MAX_LEN_TOOM_3: usize = 400;
+
+/// If the divisor or the quotient is at most this long, use simple division.
+pub(crate) const DIV_MAX_LEN_SIMPLE: usize = 32;
+
+/// If the divisor is at least this long and the quotient much longer, divide by a reciprocal.
+pub(crate) const DIV_MIN_LEN_BARRETT: usize = 1000;
+
+/// If a reciprocal is at least this long, compute it by Newton's method.
+pub(crate) const DIV_MIN_LEN_NEWTON: usize = 16000;
+
+/// If the modulus is at least this long, Montgomery reduction uses multiplication.
+pub(crate) const REDC_MIN_LEN_MUL: usize = 1000;
+
+/// Format in a non-power-of-two radix in chunks of this many words.
+pub(crate) const FMT_CHUNK_LEN: usize = 16;
+
+/// Parse in a non-power-of-two radix in chunks of this many words.
+pub(crate) const PARSE_CHUNK_LEN: usize = 256;
@@ -1,9 +1,18 @@
 #[path = "../generic/add.rs"]
 pub(crate) mod add;
 
+#[path = "../generic/add_chain.rs"]
+pub(crate) mod add_chain;
+
 #[path = "../generic/digits.rs"]
 pub(crate) mod digits;
 
+#[path = "../generic/mul.rs"]
+pub(crate) mod mul;
+
 pub(crate) mod ntt;
 
 pub(crate) mod word;
+
+#[path = "../generic/thresholds.rs"]
+pub(crate) mod thresholds;
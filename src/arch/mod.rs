@@ -3,8 +3,11 @@
 use cfg_if::cfg_if;
 
 pub(crate) use arch_impl::add;
+pub(crate) use arch_impl::add_chain;
 pub(crate) use arch_impl::digits;
+pub(crate) use arch_impl::mul;
 pub(crate) use arch_impl::ntt;
+pub(crate) use arch_impl::thresholds;
 pub(crate) use arch_impl::word;
 
 // Architecture choice. The logic works like this:
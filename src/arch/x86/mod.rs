@@ -1,10 +1,19 @@
 pub(crate) mod add;
 
+#[path = "../generic/add_chain.rs"]
+pub(crate) mod add_chain;
+
 #[path = "../generic/digits.rs"]
 pub(crate) mod digits;
 
+#[path = "../generic/mul.rs"]
+pub(crate) mod mul;
+
 #[path = "../generic_32_bit/ntt.rs"]
 pub(crate) mod ntt;
 
 #[path = "../generic_32_bit/word.rs"]
 pub(crate) mod word;
+
+#[path = "../generic/thresholds.rs"]
+pub(crate) mod thresholds;
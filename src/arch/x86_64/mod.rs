@@ -1,10 +1,20 @@
 pub(crate) mod add;
 
+#[path = "../generic/add_chain.rs"]
+pub(crate) mod add_chain;
+
 #[path = "../generic/digits.rs"]
 pub(crate) mod digits;
 
+#[path = "../generic/mul.rs"]
+mod generic_mul;
+
+pub(crate) mod mul;
+
 #[path = "../generic_64_bit/ntt.rs"]
 pub(crate) mod ntt;
 
 #[path = "../generic_64_bit/word.rs"]
 pub(crate) mod word;
+
+pub(crate) mod thresholds;
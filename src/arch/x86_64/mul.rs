@@ -0,0 +1,278 @@
+//! Multiplication kernels using `mulx`, `adcx` and `adox`.
+//!
+//! The BMI2 and ADX extensions are detected at runtime when `std` is available, otherwise they
+//! are only used if enabled at compile time. Without them the portable kernels are used.
+
+use super::generic_mul;
+use crate::arch::word::Word;
+use core::arch::asm;
+
+/// Multiply a word sequence by a `Word` in place with carry in.
+///
+/// Returns carry.
+#[inline]
+pub(crate) fn mul_word_in_place_with_carry(words: &mut [Word], rhs: Word, carry: Word) -> Word {
+    if has_adx() {
+        // SAFETY: the CPU supports BMI2 and ADX.
+        unsafe { mul_word_in_place_with_carry_adx(words, rhs, carry) }
+    } else {
+        generic_mul::mul_word_in_place_with_carry(words, rhs, carry)
+    }
+}
+
+/// words += mult * rhs
+///
+/// `words` and `rhs` must have the same length.
+///
+/// Returns carry.
+#[inline]
+pub(crate) fn add_mul_word_same_len_in_place(words: &mut [Word], mult: Word, rhs: &[Word]) -> Word {
+    if has_adx() {
+        // SAFETY: the CPU supports BMI2 and ADX.
+        unsafe { add_mul_word_same_len_in_place_adx(words, mult, rhs) }
+    } else {
+        generic_mul::add_mul_word_same_len_in_place(words, mult, rhs)
+    }
+}
+
+/// Are BMI2 and ADX enabled at compile time?
+#[cfg(all(target_feature = "bmi2", target_feature = "adx"))]
+#[inline]
+fn has_adx() -> bool {
+    true
+}
+
+/// Does the CPU support BMI2 and ADX?
+///
+/// `is_x86_feature_detected` caches the answer, so this is cheap.
+#[cfg(all(
+    not(all(target_feature = "bmi2", target_feature = "adx")),
+    feature = "std"
+))]
+#[inline]
+fn has_adx() -> bool {
+    std::is_x86_feature_detected!("bmi2") && std::is_x86_feature_detected!("adx")
+}
+
+/// Without `std` there is no runtime detection.
+#[cfg(all(
+    not(all(target_feature = "bmi2", target_feature = "adx")),
+    not(feature = "std")
+))]
+#[inline]
+fn has_adx() -> bool {
+    false
+}
+
+/// Same as `generic_mul::mul_word_in_place_with_carry`.
+///
+/// # Safety
+///
+/// The CPU must support BMI2 and ADX.
+#[target_feature(enable = "bmi2,adx")]
+unsafe fn mul_word_in_place_with_carry_adx(words: &mut [Word], rhs: Word, carry: Word) -> Word {
+    let mut carry = carry;
+    // Loop counters are decremented with `lea` and tested with `jrcxz`, which don't touch the
+    // flags, so that the carry flag survives across iterations.
+    asm!(
+        "xor {lo:e}, {lo:e}",
+        "2:",
+        "jrcxz 3f",
+        "mulx {hi}, {lo}, qword ptr [{words}]",
+        "adcx {lo}, {carry}",
+        "mov qword ptr [{words}], {lo}",
+        "mulx {carry}, {lo}, qword ptr [{words} + 8]",
+        "adcx {lo}, {hi}",
+        "mov qword ptr [{words} + 8], {lo}",
+        "mulx {hi}, {lo}, qword ptr [{words} + 16]",
+        "adcx {lo}, {carry}",
+        "mov qword ptr [{words} + 16], {lo}",
+        "mulx {carry}, {lo}, qword ptr [{words} + 24]",
+        "adcx {lo}, {hi}",
+        "mov qword ptr [{words} + 24], {lo}",
+        "lea {words}, [{words} + 32]",
+        "lea rcx, [rcx - 1]",
+        "jmp 2b",
+        "3:",
+        "mov rcx, {rem}",
+        "4:",
+        "jrcxz 5f",
+        "mulx {hi}, {lo}, qword ptr [{words}]",
+        "adcx {lo}, {carry}",
+        "mov qword ptr [{words}], {lo}",
+        "mov {carry}, {hi}",
+        "lea {words}, [{words} + 8]",
+        "lea rcx, [rcx - 1]",
+        "jmp 4b",
+        "5:",
+        "mov {lo:e}, 0",
+        "adcx {carry}, {lo}",
+        words = inout(reg) words.as_mut_ptr() => _,
+        carry = inout(reg) carry,
+        rem = in(reg) words.len() % 4,
+        lo = out(reg) _,
+        hi = out(reg) _,
+        in("rdx") rhs,
+        inout("rcx") words.len() / 4 => _,
+        options(nostack),
+    );
+    // The true carry fits in a Word, so the final carry flag addition doesn't overflow.
+    carry
+}
+
+/// Same as `generic_mul::add_mul_word_same_len_in_place`.
+///
+/// Two independent carry chains: `adcx` adds the low halves of the products into `words`, `adox`
+/// adds the high halves of the previous products.
+///
+/// # Safety
+///
+/// The CPU must support BMI2 and ADX.
+#[target_feature(enable = "bmi2,adx")]
+unsafe fn add_mul_word_same_len_in_place_adx(words: &mut [Word], mult: Word, rhs: &[Word]) -> Word {
+    assert!(words.len() == rhs.len());
+    let carry: Word;
+    // Loop counters are decremented with `lea` and tested with `jrcxz`, which don't touch the
+    // flags, so that both carry flags survive across iterations.
+    asm!(
+        "xor {carry:e}, {carry:e}",
+        "2:",
+        "jrcxz 3f",
+        "mulx {hi}, {lo}, qword ptr [{rhs}]",
+        "adcx {lo}, qword ptr [{words}]",
+        "adox {lo}, {carry}",
+        "mov qword ptr [{words}], {lo}",
+        "mulx {carry}, {lo}, qword ptr [{rhs} + 8]",
+        "adcx {lo}, qword ptr [{words} + 8]",
+        "adox {lo}, {hi}",
+        "mov qword ptr [{words} + 8], {lo}",
+        "mulx {hi}, {lo}, qword ptr [{rhs} + 16]",
+        "adcx {lo}, qword ptr [{words} + 16]",
+        "adox {lo}, {carry}",
+        "mov qword ptr [{words} + 16], {lo}",
+        "mulx {carry}, {lo}, qword ptr [{rhs} + 24]",
+        "adcx {lo}, qword ptr [{words} + 24]",
+        "adox {lo}, {hi}",
+        "mov qword ptr [{words} + 24], {lo}",
+        "lea {words}, [{words} + 32]",
+        "lea {rhs}, [{rhs} + 32]",
+        "lea rcx, [rcx - 1]",
+        "jmp 2b",
+        "3:",
+        "mov rcx, {rem}",
+        "4:",
+        "jrcxz 5f",
+        "mulx {hi}, {lo}, qword ptr [{rhs}]",
+        "adcx {lo}, qword ptr [{words}]",
+        "adox {lo}, {carry}",
+        "mov qword ptr [{words}], {lo}",
+        "mov {carry}, {hi}",
+        "lea {words}, [{words} + 8]",
+        "lea {rhs}, [{rhs} + 8]",
+        "lea rcx, [rcx - 1]",
+        "jmp 4b",
+        "5:",
+        "mov {lo:e}, 0",
+        "adcx {carry}, {lo}",
+        "adox {carry}, {lo}",
+        words = inout(reg) words.as_mut_ptr() => _,
+        rhs = inout(reg) rhs.as_ptr() => _,
+        carry = out(reg) carry,
+        rem = in(reg) words.len() % 4,
+        lo = out(reg) _,
+        hi = out(reg) _,
+        in("rdx") mult,
+        inout("rcx") words.len() / 4 => _,
+        options(nostack),
+    );
+    // The true carry fits in a Word, so the final carry flag additions don't overflow.
+    carry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Deterministic test words, biased towards values near 0 and Word::MAX.
+    fn test_words(len: usize, seed: u64) -> Vec<Word> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                match state >> 62 {
+                    0 => state >> 60,
+                    1 => Word::MAX - (state >> 60),
+                    _ => state,
+                }
+            })
+            .collect()
+    }
+
+    const MULTS: [Word; 5] = [0, 1, 2, 0x1234_5678_9abc_def1, Word::MAX];
+
+    #[test]
+    fn test_mul_word_in_place_with_carry_adx() {
+        if !has_adx() {
+            return;
+        }
+        for len in 0..40 {
+            for (i, &mult) in MULTS.iter().enumerate() {
+                let words = test_words(len, (len * 10 + i) as u64);
+                for &carry in &MULTS {
+                    let mut expected = words.clone();
+                    let expected_carry =
+                        generic_mul::mul_word_in_place_with_carry(&mut expected, mult, carry);
+                    let mut actual = words.clone();
+                    let actual_carry =
+                        unsafe { mul_word_in_place_with_carry_adx(&mut actual, mult, carry) };
+                    assert_eq!(actual, expected);
+                    assert_eq!(actual_carry, expected_carry);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_mul_word_same_len_in_place_adx() {
+        if !has_adx() {
+            return;
+        }
+        for len in 0..40 {
+            for (i, &mult) in MULTS.iter().enumerate() {
+                let words = test_words(len, (len * 10 + i) as u64);
+                let rhs = test_words(len, (len * 10 + i) as u64 + 5);
+                let mut expected = words.clone();
+                let expected_carry =
+                    generic_mul::add_mul_word_same_len_in_place(&mut expected, mult, &rhs);
+                let mut actual = words.clone();
+                let actual_carry =
+                    unsafe { add_mul_word_same_len_in_place_adx(&mut actual, mult, &rhs) };
+                assert_eq!(actual, expected);
+                assert_eq!(actual_carry, expected_carry);
+            }
+        }
+
+        let mut words = [Word::MAX; 10];
+        let carry =
+            unsafe { add_mul_word_same_len_in_place_adx(&mut words, Word::MAX, &[Word::MAX; 10]) };
+        assert_eq!(
+            words,
+            [
+                0,
+                Word::MAX,
+                Word::MAX,
+                Word::MAX,
+                Word::MAX,
+                Word::MAX,
+                Word::MAX,
+                Word::MAX,
+                Word::MAX,
+                Word::MAX
+            ]
+        );
+        assert_eq!(carry, Word::MAX);
+    }
+}
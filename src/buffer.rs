@@ -227,7 +227,7 @@ impl Clone for Buffer {
     #[inline]
     fn clone_from(&mut self, source: &Buffer) {
         assert!(self.capacity() >= source.len());
-        self.0.clone_from(&source.0);
+        (*self.0).clone_from(&*source.0);
     }
 }
 
@@ -1,19 +1,32 @@
 //! Division functions.
 
 use crate::{
-    arch::word::Word,
+    arch::{self, word::Word},
     fast_divide::FastDivideNormalized,
     memory::{self, Memory},
     primitive::{double_word, extend_word},
     shift,
 };
-use alloc::alloc::Layout;
+use alloc::{alloc::Layout, vec::Vec};
+use static_assertions::const_assert;
 
 mod divide_conquer;
+mod newton;
 mod simple;
 
 /// If divisor or quotient is at most this length, use the simple division algorithm.
-const MAX_LEN_SIMPLE: usize = 32;
+const MAX_LEN_SIMPLE: usize = arch::thresholds::DIV_MAX_LEN_SIMPLE;
+
+/// If divisor is at least this length and the quotient is long enough, use division by a
+/// reciprocal.
+const MIN_LEN_BARRETT: usize = arch::thresholds::DIV_MIN_LEN_BARRETT;
+
+/// Division by a reciprocal needs a quotient at least this many times longer than the divisor
+/// to recover the cost of computing the reciprocal.
+const MIN_QUOTIENT_RATIO_BARRETT: usize = 4;
+// Computing the reciprocal divides with a quotient as long as the divisor, which must not use
+// the reciprocal again.
+const_assert!(MIN_QUOTIENT_RATIO_BARRETT >= 2);
 
 /// Normalize a large divisor.
 ///
@@ -113,11 +126,62 @@ pub(crate) fn memory_requirement_exact(lhs_len: usize, rhs_len: usize) -> Layout
     assert!(lhs_len >= rhs_len && rhs_len >= 2);
     if rhs_len <= MAX_LEN_SIMPLE || lhs_len - rhs_len <= MAX_LEN_SIMPLE {
         memory::zero_layout()
+    } else if use_barrett(lhs_len, rhs_len) {
+        newton::memory_requirement_exact(rhs_len)
     } else {
         divide_conquer::memory_requirement_exact(lhs_len, rhs_len)
     }
 }
 
+/// Should division use a reciprocal of the divisor?
+fn use_barrett(lhs_len: usize, rhs_len: usize) -> bool {
+    rhs_len >= MIN_LEN_BARRETT && lhs_len - rhs_len >= MIN_QUOTIENT_RATIO_BARRETT * rhs_len
+}
+
+/// Reciprocal of a normalized divisor for [div_rem_in_place_with_reciprocal].
+///
+/// Returns `None` if the divisor is too short for division by a reciprocal to pay off.
+pub(crate) fn reciprocal_if_useful(rhs: &[Word]) -> Option<Vec<Word>> {
+    if rhs.len() >= MIN_LEN_BARRETT {
+        Some(newton::reciprocal(rhs))
+    } else {
+        None
+    }
+}
+
+/// Memory requirement for division with a reciprocal from [reciprocal_if_useful].
+pub(crate) fn memory_requirement_with_reciprocal_exact(lhs_len: usize, rhs_len: usize) -> Layout {
+    assert!(lhs_len >= rhs_len && rhs_len >= MIN_LEN_BARRETT);
+    if lhs_len - rhs_len <= MAX_LEN_SIMPLE {
+        memory::zero_layout()
+    } else {
+        newton::memory_requirement_exact(rhs_len)
+    }
+}
+
+/// Divide lhs by rhs using a reciprocal from [reciprocal_if_useful], replacing the top words of
+/// lhs by the quotient and the bottom words of lhs by the remainder.
+///
+/// lhs = [lhs / rhs, lhs % rhs]
+///
+/// Returns carry in the quotient. It is at most 1 because rhs is normalized.
+#[must_use]
+pub(crate) fn div_rem_in_place_with_reciprocal(
+    lhs: &mut [Word],
+    rhs: &[Word],
+    fast_div_rhs_top: FastDivideNormalized,
+    inv: &[Word],
+    memory: &mut Memory,
+) -> bool {
+    assert!(lhs.len() >= rhs.len() && rhs.len() >= MIN_LEN_BARRETT);
+
+    if lhs.len() - rhs.len() <= MAX_LEN_SIMPLE {
+        simple::div_rem_in_place(lhs, rhs, fast_div_rhs_top)
+    } else {
+        newton::div_rem_in_place_with_reciprocal(lhs, rhs, inv, memory)
+    }
+}
+
 /// Divide lhs by rhs, replacing the top words of lhs by the quotient and the
 /// bottom words of lhs by the remainder.
 ///
@@ -137,6 +201,8 @@ pub(crate) fn div_rem_in_place(
 
     if rhs.len() <= MAX_LEN_SIMPLE || lhs.len() - rhs.len() <= MAX_LEN_SIMPLE {
         simple::div_rem_in_place(lhs, rhs, fast_div_rhs_top)
+    } else if use_barrett(lhs.len(), rhs.len()) {
+        newton::div_rem_in_place(lhs, rhs, memory)
     } else {
         divide_conquer::div_rem_in_place(lhs, rhs, fast_div_rhs_top, memory)
     }
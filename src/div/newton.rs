@@ -0,0 +1,123 @@
+//! Division by a precomputed reciprocal (Barrett reduction).
+
+use crate::{
+    add,
+    arch::word::Word,
+    cmp,
+    memory::{self, Memory},
+    mul, reciprocal,
+    sign::Sign::*,
+};
+use alloc::{alloc::Layout, vec::Vec};
+use core::cmp::Ordering;
+
+/// Memory requirement for division.
+pub(crate) fn memory_requirement_exact(rhs_len: usize) -> Layout {
+    // estimate product:                2n + 2
+    // remainder:                       n + 1
+    // q * rhs:                         2n + 1
+    // multiplications with the smaller factor at most n + 1 long
+    let n = rhs_len;
+    memory::add_layout(
+        memory::array_layout::<Word>(5 * n + 4),
+        mul::memory_requirement_exact(2 * n + 2, n + 1),
+    )
+}
+
+/// floor(B^(2n) / rhs) for normalized rhs of length n, in n + 1 words.
+pub(crate) fn reciprocal(rhs: &[Word]) -> Vec<Word> {
+    let n = rhs.len();
+    let mut inv = reciprocal::reciprocal_normalized(rhs).as_words().to_vec();
+    debug_assert!(inv.len() == n + 1);
+    inv.resize(n + 1, 0);
+    inv
+}
+
+/// Division using a reciprocal of the divisor computed by Newton's method.
+///
+/// Divide lhs by rhs, replacing the top words of lhs by the quotient and the
+/// bottom words of lhs by the remainder.
+///
+/// lhs = [lhs / rhs, lhs % rhs]
+///
+/// Returns carry in the quotient. It is at most 1 because rhs is normalized.
+#[must_use]
+pub(crate) fn div_rem_in_place(lhs: &mut [Word], rhs: &[Word], memory: &mut Memory) -> bool {
+    div_rem_in_place_with_reciprocal(lhs, rhs, &reciprocal(rhs), memory)
+}
+
+/// Division using a precomputed reciprocal inv = floor(B^(2n) / rhs).
+///
+/// lhs = [lhs / rhs, lhs % rhs]
+///
+/// Returns carry in the quotient. It is at most 1 because rhs is normalized.
+#[must_use]
+pub(crate) fn div_rem_in_place_with_reciprocal(
+    lhs: &mut [Word],
+    rhs: &[Word],
+    inv: &[Word],
+    memory: &mut Memory,
+) -> bool {
+    let n = rhs.len();
+    assert!(n >= 2 && lhs.len() > n && inv.len() == n + 1);
+
+    // Divide blocks of 2n words, from the top, like long division.
+    let mut overflow = false;
+    let mut m = lhs.len();
+    while m >= 2 * n {
+        if div_rem_block(&mut lhs[m - 2 * n..m], rhs, inv, memory) {
+            assert!(m == lhs.len());
+            overflow = true;
+        }
+        m -= n;
+    }
+    if m > n {
+        let o = div_rem_block(&mut lhs[..m], rhs, inv, memory);
+        if o {
+            assert!(m == lhs.len());
+            overflow = true;
+        }
+    }
+    overflow
+}
+
+/// Divide lhs of length n + k, k <= n, by rhs of length n.
+///
+/// inv = floor(B^(2n) / rhs).
+///
+/// lhs = [lhs / rhs, lhs % rhs]
+///
+/// Returns carry in the quotient.
+#[must_use]
+fn div_rem_block(lhs: &mut [Word], rhs: &[Word], inv: &[Word], memory: &mut Memory) -> bool {
+    let n = rhs.len();
+    let k = lhs.len() - n;
+    debug_assert!(k >= 1 && k <= n && inv.len() == n + 1);
+
+    // Barrett's estimate: q = floor(floor(lhs / B^(n-1)) * inv / B^(n+1)).
+    // Because lhs < B^(2n), the true quotient is at most q + 2.
+    let (product, mut memory) = memory.allocate_slice_fill::<Word>(n + k + 2, 0);
+    let overflow = mul::add_signed_mul(product, Positive, &lhs[n - 1..], inv, &mut memory);
+    debug_assert!(overflow == 0);
+    let (rem, mut memory) = memory.allocate_slice_copy(&lhs[..n + 1]);
+    let q = &mut product[n + 1..];
+
+    // rem = lhs - q * rhs. It is less than 3 * rhs, so n + 1 words suffice.
+    {
+        let (q_rhs, mut memory) = memory.allocate_slice_fill::<Word>(n + k + 1, 0);
+        let overflow = mul::add_signed_mul(q_rhs, Positive, q, rhs, &mut memory);
+        debug_assert!(overflow == 0);
+        let _ = add::sub_same_len_in_place(rem, &q_rhs[..n + 1]);
+    }
+
+    while rem[n] != 0 || cmp::cmp_same_len(&rem[..n], rhs) != Ordering::Less {
+        let borrow = add::sub_in_place(rem, rhs);
+        debug_assert!(!borrow);
+        let carry = add::add_one_in_place(q);
+        debug_assert!(!carry);
+    }
+
+    lhs[..n].copy_from_slice(&rem[..n]);
+    lhs[n..].copy_from_slice(&q[..k]);
+    q[k] != 0
+}
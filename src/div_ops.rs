@@ -659,10 +659,8 @@ impl RemEuclid<IBig> for IBig {
     #[inline]
     fn rem_euclid(self, rhs: IBig) -> IBig {
         let r = self % &rhs;
-        match r.sign() {
-            Positive => r,
-            Negative => r + rhs.abs(),
-        }
+        let (_, modulus) = rhs.into_sign_magnitude();
+        IBig::rem_euclid_from_truncated(r, modulus)
     }
 }
 
@@ -671,11 +669,7 @@ impl RemEuclid<&IBig> for IBig {
 
     #[inline]
     fn rem_euclid(self, rhs: &IBig) -> IBig {
-        let r = self % rhs;
-        match r.sign() {
-            Positive => r,
-            Negative => r + rhs.abs(),
-        }
+        IBig::rem_euclid_from_truncated(self % rhs, rhs.magnitude())
     }
 }
 
@@ -685,10 +679,8 @@ impl RemEuclid<IBig> for &IBig {
     #[inline]
     fn rem_euclid(self, rhs: IBig) -> IBig {
         let r = self % &rhs;
-        match r.sign() {
-            Positive => r,
-            Negative => r + rhs.abs(),
-        }
+        let (_, modulus) = rhs.into_sign_magnitude();
+        IBig::rem_euclid_from_truncated(r, modulus)
     }
 }
 
@@ -697,10 +689,35 @@ impl RemEuclid<&IBig> for &IBig {
 
     #[inline]
     fn rem_euclid(self, rhs: &IBig) -> IBig {
-        let r = self % rhs;
+        IBig::rem_euclid_from_truncated(self % rhs, rhs.magnitude())
+    }
+}
+
+impl IBig {
+    /// Euclidean remainder in range `0..modulus`, given a truncated remainder `r` with
+    /// `|r| < modulus`.
+    ///
+    /// `modulus` is a `UBig` or a `&UBig`, so that an owned modulus is moved rather than copied.
+    #[inline]
+    fn rem_euclid_from_truncated<M>(r: IBig, modulus: M) -> IBig
+    where
+        IBig: From<M>,
+    {
         match r.sign() {
             Positive => r,
-            Negative => r + rhs.abs(),
+            Negative => r + IBig::from(modulus),
+        }
+    }
+
+    /// Symmetric remainder in range `(-modulus/2, modulus/2]`, given a truncated remainder `r`
+    /// with `|r| < modulus`.
+    #[inline]
+    pub(crate) fn rem_centered_from_truncated(r: IBig, modulus: &UBig) -> IBig {
+        let r = IBig::rem_euclid_from_truncated(r, modulus);
+        if *r.magnitude() > modulus >> 1 {
+            r - IBig::from(modulus)
+        } else {
+            r
         }
     }
 }
@@ -1743,6 +1760,6 @@ impl IBig {
     }
 }
 
-fn panic_divide_by_0() -> ! {
+pub(crate) fn panic_divide_by_0() -> ! {
     panic!("divide by 0")
 }
@@ -0,0 +1,246 @@
+//! Repeated division by the same divisor.
+
+use crate::{
+    arch::word::Word,
+    buffer::Buffer,
+    div,
+    div_ops::panic_divide_by_0,
+    fast_divide::FastDivideNormalized,
+    memory::MemoryAllocation,
+    primitive::extend_word,
+    shift,
+    ubig::{Repr::*, UBig},
+};
+use alloc::vec::Vec;
+
+/// A divisor prepared for fast repeated division.
+///
+/// Normalization of the divisor and the reciprocal of its top word are computed once. For large
+/// divisors a reciprocal of the whole divisor is also cached, so that long dividends are divided
+/// by multiplication (Barrett reduction).
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{ubig, Divisor};
+/// let divisor = Divisor::new(&ubig!(10));
+/// assert_eq!(divisor.div_rem(&ubig!(1234)), (ubig!(123), ubig!(4)));
+/// assert_eq!(divisor.div(&ubig!(1234)), ubig!(123));
+/// assert_eq!(divisor.rem(&ubig!(1234)), ubig!(4));
+/// ```
+pub struct Divisor(DivisorRepr);
+
+enum DivisorRepr {
+    Small(DivisorSmall),
+    Large(DivisorLarge),
+}
+
+struct DivisorSmall {
+    divisor: Word,
+    shift: u32,
+    fast_div: FastDivideNormalized,
+}
+
+struct DivisorLarge {
+    normalized_divisor: Vec<Word>,
+    shift: u32,
+    fast_div_top: FastDivideNormalized,
+    reciprocal: Option<Vec<Word>>,
+}
+
+impl Divisor {
+    /// Prepare division by `divisor`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Divisor};
+    /// let divisor = Divisor::new(&ubig!(10));
+    /// assert_eq!(divisor.divisor(), ubig!(10));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn new(divisor: &UBig) -> Divisor {
+        match divisor.repr() {
+            Small(0) => panic_divide_by_0(),
+            Small(word) => Divisor(DivisorRepr::Small(DivisorSmall::new(*word))),
+            Large(buffer) => Divisor(DivisorRepr::Large(DivisorLarge::new(buffer))),
+        }
+    }
+
+    /// The divisor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Divisor};
+    /// let divisor = Divisor::new(&ubig!(10));
+    /// assert_eq!(divisor.divisor(), ubig!(10));
+    /// ```
+    pub fn divisor(&self) -> UBig {
+        match &self.0 {
+            DivisorRepr::Small(small) => UBig::from_word(small.divisor),
+            DivisorRepr::Large(large) => large.divisor(),
+        }
+    }
+
+    /// `(lhs / divisor, lhs % divisor)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Divisor};
+    /// let divisor = Divisor::new(&ubig!(10));
+    /// assert_eq!(divisor.div_rem(&ubig!(1234)), (ubig!(123), ubig!(4)));
+    /// ```
+    pub fn div_rem(&self, lhs: &UBig) -> (UBig, UBig) {
+        match &self.0 {
+            DivisorRepr::Small(small) => {
+                let (q, r) = small.div_rem(lhs);
+                (q, UBig::from_word(r))
+            }
+            DivisorRepr::Large(large) => large.div_rem(lhs),
+        }
+    }
+
+    /// `lhs / divisor`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Divisor};
+    /// let divisor = Divisor::new(&ubig!(10));
+    /// assert_eq!(divisor.div(&ubig!(1234)), ubig!(123));
+    /// ```
+    pub fn div(&self, lhs: &UBig) -> UBig {
+        match &self.0 {
+            DivisorRepr::Small(small) => small.div_rem(lhs).0,
+            DivisorRepr::Large(large) => large.div_rem(lhs).0,
+        }
+    }
+
+    /// `lhs % divisor`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ubig, Divisor};
+    /// let divisor = Divisor::new(&ubig!(10));
+    /// assert_eq!(divisor.rem(&ubig!(1234)), ubig!(4));
+    /// ```
+    pub fn rem(&self, lhs: &UBig) -> UBig {
+        match &self.0 {
+            DivisorRepr::Small(small) => UBig::from_word(small.rem(lhs)),
+            DivisorRepr::Large(large) => large.div_rem(lhs).1,
+        }
+    }
+}
+
+impl DivisorSmall {
+    fn new(divisor: Word) -> DivisorSmall {
+        debug_assert!(divisor != 0);
+        let shift = divisor.leading_zeros();
+        DivisorSmall {
+            divisor,
+            shift,
+            fast_div: FastDivideNormalized::new(divisor << shift),
+        }
+    }
+
+    /// (lhs / divisor, lhs % divisor)
+    fn div_rem(&self, lhs: &UBig) -> (UBig, Word) {
+        match lhs.repr() {
+            Small(word) => {
+                let (q, r) = self.fast_div.div_rem(extend_word(*word) << self.shift);
+                (UBig::from_word(q), r >> self.shift)
+            }
+            Large(buffer) => {
+                let mut buffer = buffer.clone();
+                let rem = div::fast_div_by_word_in_place(&mut buffer, self.divisor, self.fast_div);
+                (buffer.into(), rem)
+            }
+        }
+    }
+
+    /// lhs % divisor
+    fn rem(&self, lhs: &UBig) -> Word {
+        let rem = match lhs.repr() {
+            Small(word) => *word,
+            Large(buffer) => div::fast_rem_by_normalized_word(buffer, self.fast_div),
+        };
+        let (_, rem) = self.fast_div.div_rem(extend_word(rem) << self.shift);
+        rem >> self.shift
+    }
+}
+
+impl DivisorLarge {
+    fn new(divisor: &[Word]) -> DivisorLarge {
+        let mut normalized_divisor = divisor.to_vec();
+        let (shift, fast_div_top) = div::normalize_large(&mut normalized_divisor);
+        let reciprocal = div::reciprocal_if_useful(&normalized_divisor);
+        DivisorLarge {
+            normalized_divisor,
+            shift,
+            fast_div_top,
+            reciprocal,
+        }
+    }
+
+    fn divisor(&self) -> UBig {
+        let mut buffer = Buffer::allocate(self.normalized_divisor.len());
+        buffer.extend(&self.normalized_divisor);
+        let low_bits = shift::shr_in_place(&mut buffer, self.shift);
+        debug_assert!(low_bits == 0);
+        buffer.into()
+    }
+
+    /// (lhs / divisor, lhs % divisor)
+    fn div_rem(&self, lhs: &UBig) -> (UBig, UBig) {
+        let n = self.normalized_divisor.len();
+        let words = lhs.as_words();
+        if words.len() < n {
+            return (UBig::from_word(0), lhs.clone());
+        }
+
+        let mut buffer = Buffer::allocate(words.len() + 1);
+        buffer.extend(words);
+        let carry = shift::shl_in_place(&mut buffer, self.shift);
+        buffer.push(carry);
+
+        let overflow = match &self.reciprocal {
+            Some(inv) => {
+                let mut allocation = MemoryAllocation::new(
+                    div::memory_requirement_with_reciprocal_exact(buffer.len(), n),
+                );
+                div::div_rem_in_place_with_reciprocal(
+                    &mut buffer,
+                    &self.normalized_divisor,
+                    self.fast_div_top,
+                    inv,
+                    &mut allocation.memory(),
+                )
+            }
+            None => {
+                let mut allocation =
+                    MemoryAllocation::new(div::memory_requirement_exact(buffer.len(), n));
+                div::div_rem_in_place(
+                    &mut buffer,
+                    &self.normalized_divisor,
+                    self.fast_div_top,
+                    &mut allocation.memory(),
+                )
+            }
+        };
+        // The top word of the shifted dividend is less than the divisor's.
+        debug_assert!(!overflow);
+
+        let mut rem = Buffer::allocate(n);
+        rem.extend(&buffer[..n]);
+        let low_bits = shift::shr_in_place(&mut rem, self.shift);
+        debug_assert!(low_bits == 0);
+        buffer.erase_front(n);
+        (buffer.into(), rem.into())
+    }
+}
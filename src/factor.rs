@@ -0,0 +1,522 @@
+//! Integer factorization.
+//!
+//! Numbers are factored by trial division by small primes, followed by Pollard's p−1 method
+//! and Pollard–Brent rho for the remaining cofactors.
+//!
+//! # Examples
+//!
+//! ```
+//! # use ibig::{factor::FactorBudget, ubig};
+//! let n = ubig!(2).pow(64) + ubig!(1);
+//! assert_eq!(n.factor(), [(ubig!(274177), 1), (ubig!(67280421310721), 1)]);
+//!
+//! // With no budget for the rho method, only the small factor is found.
+//! let budget = FactorBudget {
+//!     rho_iterations: 0,
+//!     ..FactorBudget::default()
+//! };
+//! let n = ubig!(1000000007) * ubig!(998244353) * ubig!(12);
+//! let factorization = n.factor_with_budget(budget);
+//! assert_eq!(factorization.factors(), [(ubig!(2), 2), (ubig!(3), 1)]);
+//! assert_eq!(
+//!     factorization.unfactored(),
+//!     [(ubig!(1000000007) * ubig!(998244353), 1)]
+//! );
+//! assert!(!factorization.is_complete());
+//! ```
+
+use crate::{
+    modular::{Modulo, ModuloRing},
+    ops::DivRem,
+    ubig::UBig,
+};
+use alloc::{vec, vec::Vec};
+
+/// Limits on the amount of work done by [UBig::factor_with_budget].
+///
+/// Factors that could not be split within the budget are reported as
+/// [unfactored](Factorization::unfactored).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FactorBudget {
+    /// Trial division by all primes up to this bound.
+    pub trial_division_bound: u32,
+    /// Stage 1 bound for Pollard's p−1 method. 0 disables the method.
+    pub pm1_bound: u32,
+    /// Maximum number of Pollard–Brent rho iterations for each composite cofactor.
+    pub rho_iterations: usize,
+}
+
+impl FactorBudget {
+    /// No limit on the number of rho iterations.
+    ///
+    /// Factorization with this budget always completes, but it may take a very long time
+    /// for numbers with several large prime factors.
+    pub const UNLIMITED: FactorBudget = FactorBudget {
+        trial_division_bound: 1000,
+        pm1_bound: 10000,
+        rho_iterations: usize::MAX,
+    };
+}
+
+impl Default for FactorBudget {
+    /// Finds all prime factors up to about 2^40 in a few milliseconds.
+    #[inline]
+    fn default() -> FactorBudget {
+        FactorBudget {
+            rho_iterations: 1 << 20,
+            ..FactorBudget::UNLIMITED
+        }
+    }
+}
+
+/// Possibly partial factorization of a number.
+///
+/// The product of all [factors](Factorization::factors) and all
+/// [unfactored](Factorization::unfactored) parts (with multiplicities) is the original number.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Factorization {
+    factors: Vec<(UBig, usize)>,
+    unfactored: Vec<(UBig, usize)>,
+}
+
+impl Factorization {
+    /// Prime factors with multiplicities, in increasing order.
+    ///
+    /// Primality is verified by the Miller–Rabin test with the first 12 prime bases, which is
+    /// deterministic for factors below 3.3 * 10^24.
+    #[inline]
+    pub fn factors(&self) -> &[(UBig, usize)] {
+        &self.factors
+    }
+
+    /// Composite parts that could not be factored within the budget, with multiplicities,
+    /// in increasing order.
+    #[inline]
+    pub fn unfactored(&self) -> &[(UBig, usize)] {
+        &self.unfactored
+    }
+
+    /// Whether all factors are prime.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.unfactored.is_empty()
+    }
+
+    /// Prime factors with multiplicities, in increasing order.
+    #[inline]
+    pub fn into_factors(self) -> Vec<(UBig, usize)> {
+        self.factors
+    }
+}
+
+impl UBig {
+    /// Prime factorization.
+    ///
+    /// Returns the prime factors with their multiplicities, in increasing order.
+    ///
+    /// This may take a very long time if the number has two or more very large prime factors.
+    /// Use [factor_with_budget](UBig::factor_with_budget) to limit the amount of work.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(360).factor(), [(ubig!(2), 3), (ubig!(3), 2), (ubig!(5), 1)]);
+    /// assert_eq!(ubig!(1).factor(), []);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    pub fn factor(&self) -> Vec<(UBig, usize)> {
+        self.factor_with_budget(FactorBudget::UNLIMITED)
+            .into_factors()
+    }
+
+    /// Prime factorization with limited effort.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{factor::FactorBudget, ubig};
+    /// let factorization = ubig!(360).factor_with_budget(FactorBudget::default());
+    /// assert!(factorization.is_complete());
+    /// assert_eq!(factorization.factors(), [(ubig!(2), 3), (ubig!(3), 2), (ubig!(5), 1)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    pub fn factor_with_budget(&self, budget: FactorBudget) -> Factorization {
+        if *self == UBig::from_word(0) {
+            panic!("factor(0)");
+        }
+
+        let mut factors = Vec::new();
+        let mut unfactored = Vec::new();
+
+        let rest = trial_division(self.clone(), budget.trial_division_bound, &mut factors);
+        let mut pending = match rest {
+            None => vec![],
+            Some((n, true)) => {
+                factors.push((n, 1));
+                vec![]
+            }
+            Some((n, false)) => vec![(n, 1)],
+        };
+
+        while let Some((n, mult)) = pending.pop() {
+            if is_probable_prime(&n) {
+                factors.push((n, mult));
+                continue;
+            }
+            let d = match pm1(&n, budget.pm1_bound) {
+                Some(d) => Some(d),
+                None => pollard_brent(&n, budget.rho_iterations),
+            };
+            match d {
+                None => unfactored.push((n, mult)),
+                Some(d) => {
+                    let cofactor = &n / &d;
+                    pending.push((d, mult));
+                    pending.push((cofactor, mult));
+                }
+            }
+        }
+
+        Factorization {
+            factors: merge_factors(factors),
+            unfactored: merge_factors(unfactored),
+        }
+    }
+}
+
+/// Length of a segment of the sieve of Eratosthenes.
+const SIEVE_SEGMENT_LEN: usize = 1 << 15;
+
+/// All primes up to `bound`.
+pub(crate) fn primes_up_to(bound: u32) -> Vec<u32> {
+    Primes::new(bound).collect()
+}
+
+/// Primes up to a bound in increasing order, using a segmented sieve of Eratosthenes.
+///
+/// Memory use is proportional to the square root of the bound rather than to the bound.
+pub(crate) struct Primes {
+    bound: u32,
+    /// Primes whose square is at most `bound`.
+    sieving_primes: Vec<u32>,
+    /// The current segment starts at this number.
+    segment_start: u64,
+    /// `is_composite[i]` for `segment_start + i`.
+    is_composite: Vec<bool>,
+    /// Next position to look at in the current segment.
+    pos: usize,
+}
+
+impl Primes {
+    pub(crate) fn new(bound: u32) -> Primes {
+        // The largest number whose square is at most the bound, by binary search.
+        let (mut lo, mut hi) = (0u64, 1u64 << 16);
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if mid * mid <= u64::from(bound) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let sqrt_bound = lo as usize;
+        let mut is_composite = vec![false; sqrt_bound + 1];
+        let mut sieving_primes = Vec::new();
+        for i in 2..=sqrt_bound {
+            if !is_composite[i] {
+                sieving_primes.push(i as u32);
+                let mut j = i * i;
+                while j <= sqrt_bound {
+                    is_composite[j] = true;
+                    j += i;
+                }
+            }
+        }
+
+        let mut primes = Primes {
+            bound,
+            sieving_primes,
+            segment_start: 0,
+            is_composite: Vec::with_capacity(SIEVE_SEGMENT_LEN),
+            pos: 0,
+        };
+        primes.sieve_segment(0);
+        primes
+    }
+
+    /// Sieve the segment starting at `start`.
+    fn sieve_segment(&mut self, start: u64) {
+        let end = (start + SIEVE_SEGMENT_LEN as u64).min(u64::from(self.bound) + 1);
+        self.segment_start = start;
+        self.pos = 0;
+        self.is_composite.clear();
+        self.is_composite.resize((end - start) as usize, false);
+        for i in start..end.min(2) {
+            self.is_composite[i as usize] = true;
+        }
+        for &p in &self.sieving_primes {
+            let p = u64::from(p);
+            let first = (p * p).max((start + p - 1) / p * p);
+            let mut j = first;
+            while j < end {
+                self.is_composite[(j - start) as usize] = true;
+                j += p;
+            }
+        }
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        loop {
+            while let Some(&is_composite) = self.is_composite.get(self.pos) {
+                let n = self.segment_start + self.pos as u64;
+                self.pos += 1;
+                if !is_composite {
+                    return Some(n as u32);
+                }
+            }
+            let next_start = self.segment_start + self.is_composite.len() as u64;
+            if next_start > u64::from(self.bound) {
+                return None;
+            }
+            self.sieve_segment(next_start);
+        }
+    }
+}
+
+/// Divide out all prime factors up to `bound`, appending them to `factors`.
+///
+/// Returns the remaining cofactor if it is not 1, together with whether it is known
+/// to be prime (when it is smaller than the square of the bound).
+fn trial_division(
+    mut n: UBig,
+    bound: u32,
+    factors: &mut Vec<(UBig, usize)>,
+) -> Option<(UBig, bool)> {
+    for p in Primes::new(bound) {
+        if n < UBig::from(u64::from(p) * u64::from(p)) {
+            break;
+        }
+        let mut mult = 0;
+        loop {
+            let (q, r) = (&n).div_rem(p);
+            if r != 0 {
+                break;
+            }
+            n = q;
+            mult += 1;
+        }
+        if mult != 0 {
+            factors.push((UBig::from(p), mult));
+        }
+    }
+
+    if n == UBig::from_word(1) {
+        None
+    } else {
+        let next = u64::from(bound) + 1;
+        let known_prime = n < UBig::from(next) * UBig::from(next);
+        Some((n, known_prime))
+    }
+}
+
+/// Miller–Rabin test with bases 2, 3, ..., 37.
+///
+/// Deterministic for `n < 3.3 * 10^24`.
+pub(crate) fn is_probable_prime(n: &UBig) -> bool {
+    const BASES: [u8; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if *n < UBig::from(2u8) {
+        return false;
+    }
+    for &base in BASES.iter() {
+        if *n == UBig::from(base) {
+            return true;
+        }
+        if n % base == 0 {
+            return false;
+        }
+    }
+
+    let n_minus_1 = n - UBig::from_word(1);
+    let s = n_minus_1.trailing_zeros().unwrap();
+    let d = &n_minus_1 >> s;
+    let ring = ModuloRing::new(n);
+    let one = ring.from(1);
+    let minus_one = ring.from(&n_minus_1);
+
+    BASES.iter().all(|&base| {
+        let mut x = ring.from(base).pow(&d);
+        if x == one || x == minus_one {
+            return true;
+        }
+        for _ in 1..s {
+            x = &x * &x;
+            if x == minus_one {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Pollard's p−1 method, stage 1.
+///
+/// Finds a non-trivial factor `d` of `n` if `d - 1` is `bound`-smooth for some prime `d`.
+fn pm1(n: &UBig, bound: u32) -> Option<UBig> {
+    if bound < 2 {
+        return None;
+    }
+    let ring = ModuloRing::new(n);
+    let mut a = ring.from(2);
+    for p in Primes::new(bound) {
+        // Largest power of p not exceeding the bound.
+        let mut pk = u64::from(p);
+        while pk * u64::from(p) <= u64::from(bound) {
+            pk *= u64::from(p);
+        }
+        a = a.pow(&UBig::from(pk));
+    }
+    let g = (a - ring.from(1)).residue().gcd(n);
+    if g == UBig::from_word(1) || g == *n {
+        None
+    } else {
+        Some(g)
+    }
+}
+
+/// Pollard–Brent rho method.
+///
+/// Brent, "An improved Monte Carlo factorization algorithm", 1980.
+///
+/// Finds a non-trivial factor of a composite `n` using at most `max_iterations`
+/// evaluations of the polynomial.
+fn pollard_brent(n: &UBig, mut max_iterations: usize) -> Option<UBig> {
+    // Number of differences multiplied together before taking a gcd.
+    const BATCH: usize = 128;
+
+    let one = UBig::from_word(1);
+    if n % 2u8 == 0 {
+        return Some(UBig::from_word(2));
+    }
+    let ring = ModuloRing::new(n);
+
+    for c in 1u32.. {
+        let c = ring.from(c);
+
+        let mut y = ring.from(2);
+        let mut x = y.clone();
+        let mut ys = y.clone();
+        let mut q = ring.from(1);
+        let mut g = one.clone();
+        let mut r = 1usize;
+
+        while g == one {
+            x.clone_from(&y);
+            for _ in 0..r {
+                y = rho_step(&y, &c);
+            }
+            let mut k = 0;
+            while k < r && g == one {
+                if max_iterations == 0 {
+                    return None;
+                }
+                ys.clone_from(&y);
+                let steps = BATCH.min(r - k).min(max_iterations);
+                for _ in 0..steps {
+                    y = rho_step(&y, &c);
+                    q *= &x - &y;
+                }
+                max_iterations -= steps;
+                g = q.residue().gcd(n);
+                k += steps;
+            }
+            r = r.checked_mul(2)?;
+        }
+
+        if g == *n {
+            // The batch overshot, redo it one step at a time.
+            loop {
+                ys = rho_step(&ys, &c);
+                g = (&x - &ys).residue().gcd(n);
+                if g != one {
+                    break;
+                }
+            }
+        }
+        if g != *n {
+            return Some(g);
+        }
+        // Cycle mod n found before a cycle mod a factor. Try another polynomial.
+    }
+    unreachable!()
+}
+
+/// x^2 + c
+#[inline]
+fn rho_step<'a>(x: &Modulo<'a>, c: &Modulo<'a>) -> Modulo<'a> {
+    x * x + c
+}
+
+/// Sort factors and combine multiplicities of equal factors.
+fn merge_factors(mut factors: Vec<(UBig, usize)>) -> Vec<(UBig, usize)> {
+    factors.sort();
+    let mut merged: Vec<(UBig, usize)> = Vec::with_capacity(factors.len());
+    for (factor, mult) in factors {
+        match merged.last_mut() {
+            Some((last, last_mult)) if *last == factor => *last_mult += mult,
+            _ => merged.push((factor, mult)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primes_up_to() {
+        assert_eq!(primes_up_to(0), []);
+        assert_eq!(primes_up_to(1), []);
+        assert_eq!(primes_up_to(2), [2]);
+        assert_eq!(primes_up_to(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        // Across segment boundaries.
+        assert_eq!(primes_up_to(32767).len(), 3512);
+        assert_eq!(primes_up_to(32768).len(), 3512);
+        assert_eq!(primes_up_to(1000000).len(), 78498);
+    }
+
+    #[test]
+    fn test_merge_factors() {
+        let factors = vec![
+            (UBig::from(5u8), 1),
+            (UBig::from(2u8), 2),
+            (UBig::from(5u8), 3),
+        ];
+        assert_eq!(
+            merge_factors(factors),
+            [(UBig::from(2u8), 2), (UBig::from(5u8), 4)]
+        );
+    }
+
+    #[test]
+    fn test_pm1() {
+        // 1000000007 - 1 = 2 * 500000003 and 998244353 - 1 = 2^23 * 7 * 17 are not
+        // 1000-powersmooth, 101 - 1 = 2^2 * 5^2 is 100-powersmooth.
+        let n = UBig::from(1000000007u32) * UBig::from(998244353u32);
+        assert_eq!(pm1(&n, 1000), None);
+        let n = UBig::from(1000000007u32) * UBig::from(998244353u32) * UBig::from(101u8);
+        assert_eq!(pm1(&n, 100), Some(UBig::from(101u8)));
+    }
+}
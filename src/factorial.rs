@@ -0,0 +1,247 @@
+//! Factorials, binomial and multinomial coefficients.
+//!
+//! The results are computed from their prime factorizations: the exponent of every prime is
+//! found using Legendre's formula, and the prime powers are combined by repeated squaring of
+//! products of primes. Binomial coefficients with a small `k` are instead computed as
+//! `(n-k+1) * ... * n / k!`, which doesn't need the primes up to `n`. The products are computed
+//! using balanced product trees, so that large multiplications have operands of similar length.
+
+use crate::{factor, primitive::PrimitiveUnsigned, ubig::UBig};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// If `k^2 / n` is at least this, compute `binomial(n, k)` from the prime factorization.
+///
+/// The product of `k` factors and the division by `k!` take time growing faster than `k`, the
+/// prime factorization takes time about proportional to `n`.
+const MIN_K_SQUARED_OVER_N_LEGENDRE: u64 = 64;
+
+/// Factorial.
+///
+/// `n! = 1 * 2 * ... * n`
+///
+/// # Example
+///
+/// ```
+/// # use ibig::{factorial, ubig};
+/// assert_eq!(factorial(0), ubig!(1));
+/// assert_eq!(factorial(5), ubig!(120));
+/// ```
+pub fn factorial(n: usize) -> UBig {
+    product_of_powers(
+        primes_up_to(n)
+            .into_iter()
+            .map(|p| (p, legendre_exponent(n, p))),
+    )
+}
+
+/// Double factorial.
+///
+/// `n!! = n * (n - 2) * (n - 4) * ...` down to 1 or 2.
+///
+/// # Example
+///
+/// ```
+/// # use ibig::{double_factorial, ubig};
+/// assert_eq!(double_factorial(0), ubig!(1));
+/// assert_eq!(double_factorial(7), ubig!(105));
+/// assert_eq!(double_factorial(8), ubig!(384));
+/// ```
+pub fn double_factorial(n: usize) -> UBig {
+    // If n = 2m, n!! = 2^m * m!
+    // If n = 2m + 1, n!! = n! / (2^m * m!)
+    let m = n / 2;
+    if n % 2 == 0 {
+        factorial(m) << m
+    } else {
+        // n!! is odd.
+        product_of_powers(
+            primes_up_to(n)
+                .into_iter()
+                .filter(|&p| p != 2)
+                .map(|p| (p, legendre_exponent(n, p) - legendre_exponent(m, p))),
+        )
+    }
+}
+
+/// Binomial coefficient.
+///
+/// `binomial(n, k) = n! / (k! * (n-k)!)` is the number of `k`-element subsets of an `n`-element
+/// set. It is 0 if `k > n`.
+///
+/// # Example
+///
+/// ```
+/// # use ibig::{binomial, ubig};
+/// assert_eq!(binomial(5, 2), ubig!(10));
+/// assert_eq!(binomial(5, 6), ubig!(0));
+/// ```
+pub fn binomial(n: usize, k: usize) -> UBig {
+    if k > n {
+        return UBig::from_word(0);
+    }
+    let k = k.min(n - k);
+    if k == 0 {
+        return UBig::from_word(1);
+    }
+    if use_legendre(n, k) {
+        multinomial_legendre(n, &[k, n - k])
+    } else {
+        // (n-k+1) * ... * n / k!
+        product_range(n - k + 1, n) / factorial(k)
+    }
+}
+
+/// Multinomial coefficient.
+///
+/// `multinomial(&[k_1, ..., k_m]) = (k_1 + ... + k_m)! / (k_1! * ... * k_m!)` is the number of
+/// ways to split a set of `k_1 + ... + k_m` elements into subsets of sizes `k_1, ..., k_m`.
+///
+/// # Example
+///
+/// ```
+/// # use ibig::{multinomial, ubig};
+/// assert_eq!(multinomial(&[2, 1, 1]), ubig!(12));
+/// assert_eq!(multinomial(&[]), ubig!(1));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the sum of `ks` overflows `usize`.
+pub fn multinomial(ks: &[usize]) -> UBig {
+    let n = ks
+        .iter()
+        .try_fold(0usize, |sum, &k| sum.checked_add(k))
+        .unwrap_or_else(|| UBig::panic_number_too_large());
+    let (max_index, max_k) = match ks.iter().enumerate().max_by_key(|&(_, &k)| k) {
+        None => return UBig::from_word(1),
+        Some((i, &k)) => (i, k),
+    };
+    if use_legendre(n, n - max_k) {
+        multinomial_legendre(n, ks)
+    } else {
+        // Starting from the largest k, multiply by binomial(sum, k) for each of the other ks.
+        let mut sum = max_k;
+        let mut res = UBig::from_word(1);
+        for (i, &k) in ks.iter().enumerate() {
+            if i != max_index {
+                sum += k;
+                res *= binomial(sum, k);
+            }
+        }
+        res
+    }
+}
+
+/// Whether to compute `binomial(n, k)`, or a multinomial coefficient where the ks other than
+/// the largest sum to `k`, from the prime factorization.
+///
+/// The prime factorization needs all primes up to `n`. For small `k` it is cheaper to multiply
+/// the `k` largest factors of `n!` and divide by `k!`.
+fn use_legendre(n: usize, k: usize) -> bool {
+    match u32::try_from(n) {
+        // The sieve is limited to u32, and the product is cheaper anyway unless k is huge.
+        Err(_) => false,
+        Ok(n) => (k as u64) * (k as u64) >= MIN_K_SQUARED_OVER_N_LEGENDRE * u64::from(n),
+    }
+}
+
+/// Multinomial coefficient from the prime factorization, where `n` is the sum of `ks`.
+fn multinomial_legendre(n: usize, ks: &[usize]) -> UBig {
+    product_of_powers(primes_up_to(n).into_iter().map(|p| {
+        let exponent = ks.iter().map(|&k| legendre_exponent(k, p)).sum::<usize>();
+        (p, legendre_exponent(n, p) - exponent)
+    }))
+}
+
+/// All primes up to `n`.
+fn primes_up_to(n: usize) -> Vec<u32> {
+    // n! for n > u32::MAX is too large to compute anyway.
+    let bound = u32::try_from(n).unwrap_or_else(|_| UBig::panic_number_too_large());
+    factor::primes_up_to(bound)
+}
+
+/// The exponent of prime `p` in `n!`.
+fn legendre_exponent(n: usize, p: u32) -> usize {
+    let p = p as usize;
+    let mut exponent = 0;
+    let mut m = n;
+    while m >= p {
+        m /= p;
+        exponent += m;
+    }
+    exponent
+}
+
+/// Product of `p^e` for `(p, e)` in `prime_powers`.
+fn product_of_powers<I>(prime_powers: I) -> UBig
+where
+    I: Iterator<Item = (u32, usize)>,
+{
+    let mut twos = 0;
+    let mut odd_prime_powers = Vec::new();
+    for (p, e) in prime_powers {
+        if p == 2 {
+            twos = e;
+        } else if e != 0 {
+            odd_prime_powers.push((p, e));
+        }
+    }
+
+    // Process exponents bit by bit, starting with the most significant bit.
+    let max_exponent = odd_prime_powers.iter().map(|&(_, e)| e).max().unwrap_or(0);
+    let mut res = UBig::from_word(1);
+    for bit in (0..usize::BIT_SIZE - max_exponent.leading_zeros()).rev() {
+        res = res.square();
+        let primes: Vec<u32> = odd_prime_powers
+            .iter()
+            .filter(|&&(_, e)| e >> bit & 1 != 0)
+            .map(|&(p, _)| p)
+            .collect();
+        res *= product(&pack(&primes));
+    }
+    res << twos
+}
+
+/// Pack factors into as few `u64` factors as possible, keeping their order.
+fn pack(factors: &[u32]) -> Vec<u64> {
+    let mut packed = Vec::new();
+    let mut acc: u64 = 1;
+    for &factor in factors {
+        match acc.checked_mul(u64::from(factor)) {
+            Some(x) => acc = x,
+            None => {
+                packed.push(acc);
+                acc = u64::from(factor);
+            }
+        }
+    }
+    packed.push(acc);
+    packed
+}
+
+/// `lo * (lo+1) * ... * hi` using a balanced product tree, for `lo <= hi`.
+fn product_range(lo: usize, hi: usize) -> UBig {
+    const MAX_LEN_SIMPLE: usize = 16;
+
+    if hi - lo < MAX_LEN_SIMPLE {
+        (lo..=hi).fold(UBig::from_word(1), |res, factor| res * factor)
+    } else {
+        let mid = lo + (hi - lo) / 2;
+        product_range(lo, mid) * product_range(mid + 1, hi)
+    }
+}
+
+/// Product of factors using a balanced product tree.
+fn product(factors: &[u64]) -> UBig {
+    const MAX_LEN_SIMPLE: usize = 16;
+
+    if factors.len() <= MAX_LEN_SIMPLE {
+        factors
+            .iter()
+            .fold(UBig::from_word(1), |res, &factor| res * factor)
+    } else {
+        let (lo, hi) = factors.split_at(factors.len() / 2);
+        product(lo) * product(hi)
+    }
+}
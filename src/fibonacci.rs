@@ -0,0 +1,144 @@
+//! Fibonacci and Lucas numbers.
+//!
+//! The numbers are computed by fast doubling using two squarings per bit of `n`:
+//!
+//! * `F(2k-1) = F(k)^2 + F(k-1)^2`
+//! * `F(2k+1) = 4 F(k)^2 - F(k-1)^2 + 2 (-1)^k`
+//! * `F(2k) = F(2k+1) - F(2k-1)`
+
+use crate::{
+    modular::{Modulo, ModuloRing},
+    primitive::PrimitiveUnsigned,
+    ubig::UBig,
+};
+
+/// Fibonacci number.
+///
+/// `F(0) = 0`, `F(1) = 1`, `F(n) = F(n-1) + F(n-2)`.
+///
+/// # Example
+///
+/// ```
+/// # use ibig::{fibonacci, ubig};
+/// assert_eq!(fibonacci(0), ubig!(0));
+/// assert_eq!(fibonacci(10), ubig!(55));
+/// ```
+pub fn fibonacci(n: usize) -> UBig {
+    if n == 0 {
+        UBig::from_word(0)
+    } else {
+        fibonacci_and_previous(n).0
+    }
+}
+
+/// Consecutive Fibonacci numbers `(F(n), F(n+1))`.
+///
+/// # Example
+///
+/// ```
+/// # use ibig::{fibonacci_pair, ubig};
+/// assert_eq!(fibonacci_pair(10), (ubig!(55), ubig!(89)));
+/// ```
+pub fn fibonacci_pair(n: usize) -> (UBig, UBig) {
+    let n1 = n
+        .checked_add(1)
+        .unwrap_or_else(|| UBig::panic_number_too_large());
+    let (f1, f0) = fibonacci_and_previous(n1);
+    (f0, f1)
+}
+
+/// Lucas number.
+///
+/// `L(0) = 2`, `L(1) = 1`, `L(n) = L(n-1) + L(n-2)`.
+///
+/// # Example
+///
+/// ```
+/// # use ibig::{lucas, ubig};
+/// assert_eq!(lucas(0), ubig!(2));
+/// assert_eq!(lucas(10), ubig!(123));
+/// ```
+pub fn lucas(n: usize) -> UBig {
+    if n == 0 {
+        UBig::from_word(2)
+    } else {
+        // L(n) = F(n) + 2 F(n-1)
+        let (f1, f0) = fibonacci_and_previous(n);
+        f1 + (f0 << 1)
+    }
+}
+
+/// Fibonacci number modulo `ring.modulus()`.
+///
+/// # Example
+///
+/// ```
+/// # use ibig::{fibonacci, fibonacci_mod, modular::ModuloRing, ubig};
+/// let ring = ModuloRing::new(&ubig!(1000));
+/// assert_eq!(fibonacci_mod(&ubig!(10), &ring), ring.from(55));
+/// // Fibonacci numbers modulo 1000 have period 1500.
+/// let n = ubig!(10).pow(100);
+/// assert_eq!(fibonacci_mod(&n, &ring), ring.from(fibonacci(1000)));
+/// ```
+pub fn fibonacci_mod<'a>(n: &UBig, ring: &'a ModuloRing) -> Modulo<'a> {
+    let bit_len = n.bit_len();
+    if bit_len == 0 {
+        return ring.from(0u8);
+    }
+    let two = ring.from(2u8);
+    // (F(k), F(k-1)) for k = 1.
+    let mut f1 = ring.from(1u8);
+    let mut f0 = ring.from(0u8);
+    let mut k_odd = true;
+    for i in (0..bit_len - 1).rev() {
+        let s1 = &f1 * &f1;
+        let s0 = &f0 * &f0;
+        let f_minus = &s1 + &s0;
+        let mut f_plus = (&s1 + &s1) + (&s1 + &s1) - s0;
+        if k_odd {
+            f_plus -= &two;
+        } else {
+            f_plus += &two;
+        }
+        if n.bit(i) {
+            f0 = &f_plus - &f_minus;
+            f1 = f_plus;
+        } else {
+            f1 = f_plus - &f_minus;
+            f0 = f_minus;
+        }
+        k_odd = n.bit(i);
+    }
+    f1
+}
+
+/// `(F(n), F(n-1))` for `n >= 1`.
+fn fibonacci_and_previous(n: usize) -> (UBig, UBig) {
+    debug_assert!(n >= 1);
+    // (F(k), F(k-1)) for k = 1.
+    let mut f1 = UBig::from_word(1);
+    let mut f0 = UBig::from_word(0);
+    let mut k_odd = true;
+    for i in (0..usize::BIT_SIZE - 1 - n.leading_zeros()).rev() {
+        let s1 = f1.square();
+        let s0 = f0.square();
+        let f_minus = &s1 + &s0;
+        // 4 F(k)^2 - F(k-1)^2 >= 2 for k >= 1.
+        let mut f_plus = (s1 << 2) - s0;
+        if k_odd {
+            f_plus -= UBig::from_word(2);
+        } else {
+            f_plus += UBig::from_word(2);
+        }
+        let bit = n & (1 << i) != 0;
+        if bit {
+            f0 = &f_plus - &f_minus;
+            f1 = f_plus;
+        } else {
+            f1 = f_plus - &f_minus;
+            f0 = f_minus;
+        }
+        k_odd = bit;
+    }
+    (f1, f0)
+}
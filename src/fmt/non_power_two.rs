@@ -1,7 +1,7 @@
 //! Format in a non-power-of-two radix.
 
 use crate::{
-    arch::word::Word,
+    arch::{self, word::Word},
     div,
     fmt::{digit_writer::DigitWriter, InRadixFull, PreparedForFormatting},
     ops::DivRem,
@@ -14,9 +14,14 @@ use core::{
     mem,
 };
 use static_assertions::const_assert;
+#[cfg(feature = "parallel")]
+use {
+    crate::{parallel, radix::DigitCase},
+    alloc::string::String,
+};
 
 /// Format in chunks of CHUNK_LEN * digits_per_word.
-const CHUNK_LEN: usize = 16;
+const CHUNK_LEN: usize = arch::thresholds::FMT_CHUNK_LEN;
 
 impl InRadixFull<'_> {
     pub(crate) fn fmt_non_power_two(&self, f: &mut Formatter) -> fmt::Result {
@@ -220,11 +225,40 @@ impl PreparedLarge {
             self.write_chunk(digit_writer, x)
         } else {
             let (q, r) = x.div_rem(&self.radix_powers[i - 1]);
+
+            #[cfg(feature = "parallel")]
+            {
+                if self.radix_powers[i - 1].len() >= parallel::MIN_LEN {
+                    let digit_case = digit_writer.digit_case();
+                    let (q_digits, r_digits) = rayon::join(
+                        || self.big_chunk_to_string(digit_case, i - 1, q),
+                        || self.big_chunk_to_string(digit_case, i - 1, r),
+                    );
+                    digit_writer.write_ascii(&q_digits?)?;
+                    return digit_writer.write_ascii(&r_digits?);
+                }
+            }
+
             self.write_big_chunk(digit_writer, i - 1, q)?;
             self.write_big_chunk(digit_writer, i - 1, r)
         }
     }
 
+    /// Format (digits_per_word * CHUNK_LEN) << i digits into a new string.
+    #[cfg(feature = "parallel")]
+    fn big_chunk_to_string(
+        &self,
+        digit_case: DigitCase,
+        i: usize,
+        x: UBig,
+    ) -> Result<String, fmt::Error> {
+        let mut digits = String::new();
+        let mut digit_writer = DigitWriter::new(&mut digits, digit_case);
+        self.write_big_chunk(&mut digit_writer, i, x)?;
+        digit_writer.flush()?;
+        Ok(digits)
+    }
+
     /// Write digits_per_word * CHUNK_LEN digits.
     fn write_chunk(&self, digit_writer: &mut DigitWriter, x: UBig) -> fmt::Result {
         let radix_info = radix::radix_info(self.radix);
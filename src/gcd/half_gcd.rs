@@ -0,0 +1,107 @@
+//! Subquadratic half-gcd algorithm.
+//!
+//! `reduce(a, b)` computes a reduction matrix that roughly halves the length of `a`, by
+//! recursively reducing the top half of the numbers twice, with a division step in between.
+//! The cost is `O(M(n) log n)` where `M(n)` is the cost of multiplication.
+
+use crate::{
+    arch::word::Word,
+    gcd::{helpers, lehmer, matrix::Matrix},
+};
+use alloc::vec::Vec;
+use core::mem;
+
+/// Below this length (in words) `reduce` uses Lehmer's algorithm.
+pub(crate) const THRESHOLD: usize = 512;
+
+/// Reduce `(a, b)`, `a >= b`, in place.
+///
+/// Returns the reduction matrix `M` such that `(a_old, b_old) = M (a, b)`. `M` satisfies
+/// `Matrix::is_safe_for(a, b)` unless it is the identity, so it may also be applied to
+/// numbers that `(a_old, b_old)` is a truncation of.
+pub(crate) fn reduce(a: &mut Vec<Word>, b: &mut Vec<Word>) -> Matrix {
+    let n = a.len();
+    if n < THRESHOLD {
+        return reduce_lehmer(a, b);
+    }
+
+    // Reduce the top half, which reduces the numbers to about 3/4 of the length.
+    let mut m = Matrix::identity();
+    let k = n / 2;
+    if b.len() > k {
+        let (mut a_top, mut b_top) = truncate(a, b, k);
+        let m_top = reduce(&mut a_top, &mut b_top);
+        if m_top.is_identity() {
+            return m;
+        }
+        let (new_a, new_b) = m_top.apply(a, b);
+        if !m_top.is_safe_for(&new_a, &new_b) {
+            return m;
+        }
+        *a = new_a;
+        *b = new_b;
+        m = m_top;
+    }
+
+    // One division step.
+    if b.is_empty() {
+        return m;
+    }
+    let mut r = a.clone();
+    let q = helpers::div_rem_in_place(&mut r, b);
+    let m_div = m.mul_quotient(&q);
+    if !m_div.is_safe_for(b, &r) {
+        return m;
+    }
+    *a = mem::replace(b, r);
+    m = m_div;
+
+    // Reduce the top of the remaining part so that the final length is about n / 2.
+    let k = n - a.len();
+    if a.len() >= k + 2 && b.len() > k {
+        let (mut a_top, mut b_top) = truncate(a, b, k);
+        let m_top = reduce(&mut a_top, &mut b_top);
+        if !m_top.is_identity() {
+            let (new_a, new_b) = m_top.apply(a, b);
+            let m_total = m.mul(&m_top);
+            if m_total.is_safe_for(&new_a, &new_b) {
+                *a = new_a;
+                *b = new_b;
+                m = m_total;
+            }
+        }
+    }
+    m
+}
+
+/// `reduce` using Lehmer steps.
+fn reduce_lehmer(a: &mut Vec<Word>, b: &mut Vec<Word>) -> Matrix {
+    let mut m = Matrix::identity();
+    while !b.is_empty() {
+        let (new_a, new_b, new_m) = match lehmer::step(a, b) {
+            Some(step) => {
+                let (new_a, new_b) = step.apply(a, b);
+                (new_a, new_b, m.mul_word_matrix(&step))
+            }
+            None => {
+                let mut r = a.clone();
+                let q = helpers::div_rem_in_place(&mut r, b);
+                (b.clone(), r, m.mul_quotient(&q))
+            }
+        };
+        if !new_m.is_safe_for(&new_a, &new_b) {
+            break;
+        }
+        *a = new_a;
+        *b = new_b;
+        m = new_m;
+    }
+    m
+}
+
+/// Drop the lowest `k` words of `a` and `b`.
+fn truncate(a: &[Word], b: &[Word], k: usize) -> (Vec<Word>, Vec<Word>) {
+    let mut b_top = b[k..].to_vec();
+    helpers::trim(&mut b_top);
+    (a[k..].to_vec(), b_top)
+}
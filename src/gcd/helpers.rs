@@ -0,0 +1,156 @@
+//! Arithmetic on word vectors used by the large gcd algorithms.
+//!
+//! All numbers are stored as little-endian `Vec<Word>` without leading zero words.
+
+use crate::{
+    add,
+    arch::{self, word::Word},
+    cmp, div,
+    memory::MemoryAllocation,
+    mul,
+    primitive::{extend_word, split_double_word},
+    shift,
+    sign::Sign::*,
+};
+use alloc::{vec, vec::Vec};
+use core::{cmp::Ordering, mem};
+
+/// Remove leading zero words.
+pub(crate) fn trim(words: &mut Vec<Word>) {
+    while let Some(0) = words.last() {
+        words.pop();
+    }
+}
+
+/// Compare two numbers.
+pub(crate) fn cmp(a: &[Word], b: &[Word]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| cmp::cmp_same_len(a, b))
+}
+
+/// a + b
+pub(crate) fn add(a: &[Word], b: &[Word]) -> Vec<Word> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut res = a.to_vec();
+    if add::add_in_place(&mut res, b) {
+        res.push(1);
+    }
+    res
+}
+
+/// a - b
+///
+/// Panics if a < b.
+pub(crate) fn sub(a: &[Word], b: &[Word]) -> Vec<Word> {
+    assert!(a.len() >= b.len());
+    let mut res = a.to_vec();
+    let borrow = add::sub_in_place(&mut res, b);
+    assert!(!borrow);
+    trim(&mut res);
+    res
+}
+
+/// a * b
+pub(crate) fn mul(a: &[Word], b: &[Word]) -> Vec<Word> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let res_len = a.len() + b.len();
+    let mut res = vec![0; res_len];
+    let mut allocation =
+        MemoryAllocation::new(mul::memory_requirement_exact(res_len, a.len().min(b.len())));
+    let mut memory = allocation.memory();
+    let overflow = mul::add_signed_mul(&mut res, Positive, a, b, &mut memory);
+    assert!(overflow == 0);
+    trim(&mut res);
+    res
+}
+
+/// x * a + y * b
+pub(crate) fn mul_add_words(x: Word, a: &[Word], y: Word, b: &[Word]) -> Vec<Word> {
+    let n = a.len().max(b.len());
+    let mut res = Vec::with_capacity(n + 2);
+    let mut carry_a: Word = 0;
+    let mut carry_b: Word = 0;
+    let mut carry = false;
+    for i in 0..n {
+        let word_a = a.get(i).copied().unwrap_or(0);
+        let word_b = b.get(i).copied().unwrap_or(0);
+        let (lo_a, hi_a) =
+            split_double_word(extend_word(x) * extend_word(word_a) + extend_word(carry_a));
+        let (lo_b, hi_b) =
+            split_double_word(extend_word(y) * extend_word(word_b) + extend_word(carry_b));
+        let (sum, c) = arch::add::add_with_carry(lo_a, lo_b, carry);
+        res.push(sum);
+        carry_a = hi_a;
+        carry_b = hi_b;
+        carry = c;
+    }
+    let (lo, hi) = split_double_word(
+        extend_word(carry_a) + extend_word(carry_b) + extend_word(Word::from(carry)),
+    );
+    res.push(lo);
+    res.push(hi);
+    trim(&mut res);
+    res
+}
+
+/// res = x * a - y * b
+///
+/// a, b and res must have the same length and the result must fit in res.
+pub(crate) fn sub_mul_words(res: &mut [Word], x: Word, a: &[Word], y: Word, b: &[Word]) {
+    debug_assert!(a.len() == res.len() && b.len() == res.len());
+    let mut carry_a: Word = 0;
+    let mut carry_b: Word = 0;
+    let mut borrow = false;
+    for ((r, word_a), word_b) in res.iter_mut().zip(a).zip(b) {
+        let (lo_a, hi_a) =
+            split_double_word(extend_word(x) * extend_word(*word_a) + extend_word(carry_a));
+        let (lo_b, hi_b) =
+            split_double_word(extend_word(y) * extend_word(*word_b) + extend_word(carry_b));
+        let (diff, borrow1) = arch::add::sub_with_borrow(lo_a, lo_b, borrow);
+        *r = diff;
+        carry_a = hi_a;
+        carry_b = hi_b;
+        borrow = borrow1;
+    }
+    debug_assert!(extend_word(carry_a) == extend_word(carry_b) + extend_word(Word::from(borrow)));
+}
+
+/// a = a % b
+///
+/// Requires b != 0.
+///
+/// Returns a / b.
+pub(crate) fn div_rem_in_place(a: &mut Vec<Word>, b: &[Word]) -> Vec<Word> {
+    debug_assert!(!b.is_empty());
+    if a.len() < b.len() {
+        return Vec::new();
+    }
+    if b.len() == 1 {
+        let rem = div::div_by_word_in_place(a, b[0]);
+        let mut quotient = mem::replace(a, vec![rem]);
+        trim(a);
+        trim(&mut quotient);
+        return quotient;
+    }
+    let mut rhs = b.to_vec();
+    let (shift, fast_div_rhs_top) = div::normalize_large(&mut rhs);
+    let carry = shift::shl_in_place(a, shift);
+    if carry != 0 {
+        a.push(carry);
+    }
+    let n = rhs.len();
+    let mut allocation = MemoryAllocation::new(div::memory_requirement_exact(a.len(), n));
+    let mut memory = allocation.memory();
+    let overflow = div::div_rem_in_place(a, &rhs, fast_div_rhs_top, &mut memory);
+    let mut quotient = a[n..].to_vec();
+    if overflow {
+        quotient.push(1);
+    }
+    a.truncate(n);
+    let low_bits = shift::shr_in_place(a, shift);
+    debug_assert!(low_bits == 0);
+    trim(a);
+    trim(&mut quotient);
+    quotient
+}
@@ -0,0 +1,72 @@
+//! Lehmer's gcd algorithm.
+
+use crate::{
+    arch::word::{DoubleWord, Word},
+    gcd::matrix::WordMatrix,
+    primitive::{double_word, extend_word, WORD_BITS},
+    sign::Sign::*,
+};
+
+/// Compute a reduction matrix for `(a, b)`, `a >= b`, from their top two words.
+///
+/// Euclid's algorithm is run on the top `2 * WORD_BITS` bits of `a` and `b` (truncated at the
+/// same position) as long as the resulting matrix is guaranteed to also be valid for the full
+/// numbers, and its entries fit in single words.
+///
+/// Returns `None` if not even one step can be guaranteed correct.
+pub(crate) fn step(a: &[Word], b: &[Word]) -> Option<WordMatrix> {
+    debug_assert!(!a.is_empty() && b.len() <= a.len());
+    let shift = a.last().unwrap().leading_zeros();
+    let mut x = top_bits(a, a.len(), shift);
+    let mut y = top_bits(b, a.len(), shift);
+
+    // [[m00, m01], [m10, m11]]
+    let mut m: [[DoubleWord; 2]; 2] = [[1, 0], [0, 1]];
+    let mut det = Positive;
+    let mut best = None;
+
+    while y != 0 {
+        let q = x / y;
+        let r = x - q * y;
+        // The entries are bounded by x / y, so this doesn't overflow.
+        m = [
+            [q * m[0][0] + m[0][1], m[0][0]],
+            [q * m[1][0] + m[1][1], m[1][0]],
+        ];
+        det = -det;
+        x = y;
+        y = r;
+
+        // The same condition as `Matrix::is_safe_for`.
+        // From then on y only decreases and m00 only increases.
+        if y < m[0][0] {
+            break;
+        }
+        // x * m00 <= top bits of a, and m00 <= y < x, so m00 fits in a Word.
+        if x - y >= 2 * m[0][0] {
+            best = Some(WordMatrix {
+                m: [
+                    [m[0][0] as Word, m[0][1] as Word],
+                    [m[1][0] as Word, m[1][1] as Word],
+                ],
+                det,
+            });
+        }
+    }
+    best
+}
+
+/// Top `2 * WORD_BITS` bits of `words` after shifting left by `shift` bits, assuming
+/// the number has `len` words.
+fn top_bits(words: &[Word], len: usize, shift: u32) -> DoubleWord {
+    let word = |i: usize| match len.checked_sub(i) {
+        Some(j) => words.get(j).copied().unwrap_or(0),
+        None => 0,
+    };
+    let top = double_word(word(2), word(1)) << shift;
+    if shift == 0 {
+        top
+    } else {
+        top | extend_word(word(3)) >> (WORD_BITS - shift)
+    }
+}
@@ -0,0 +1,148 @@
+//! Reduction matrices.
+//!
+//! A reduction matrix is a product of matrices `[[q, 1], [1, 0]]` with `q >= 1`, one for each
+//! step `(a, b) -> (b, a - q * b)` of a Euclid-like algorithm. If `M` reduces `(a, b)` to
+//! `(a', b')` then `(a, b) = M * (a', b')`.
+//!
+//! All entries are non-negative, the determinant is `±1`, and the entries are ordered:
+//! `m00 >= m01 >= m11` and `m00 >= m10 >= m11`.
+
+use crate::{
+    arch::word::Word,
+    gcd::helpers,
+    sign::Sign::{self, *},
+};
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering::*;
+
+/// Reduction matrix with single-word entries.
+pub(crate) struct WordMatrix {
+    pub(crate) m: [[Word; 2]; 2],
+    /// Determinant.
+    pub(crate) det: Sign,
+}
+
+impl WordMatrix {
+    /// M^-1 * (a, b)
+    ///
+    /// The result must be non-negative.
+    pub(crate) fn apply(&self, a: &[Word], b: &[Word]) -> (Vec<Word>, Vec<Word>) {
+        let [[m00, m01], [m10, m11]] = self.m;
+        debug_assert!(a.len() >= b.len());
+        let n = a.len();
+        let mut b_padded = b.to_vec();
+        b_padded.resize(n, 0);
+        let b = &b_padded[..];
+        let mut new_a = vec![0; n];
+        let mut new_b = vec![0; n];
+        match self.det {
+            Positive => {
+                helpers::sub_mul_words(&mut new_a, m11, a, m01, b);
+                helpers::sub_mul_words(&mut new_b, m00, b, m10, a);
+            }
+            Negative => {
+                helpers::sub_mul_words(&mut new_a, m01, b, m11, a);
+                helpers::sub_mul_words(&mut new_b, m10, a, m00, b);
+            }
+        }
+        helpers::trim(&mut new_a);
+        helpers::trim(&mut new_b);
+        (new_a, new_b)
+    }
+}
+
+/// Reduction matrix with multi-word entries.
+pub(crate) struct Matrix {
+    pub(crate) m: [[Vec<Word>; 2]; 2],
+    /// Determinant.
+    pub(crate) det: Sign,
+}
+
+impl Matrix {
+    /// Identity matrix.
+    pub(crate) fn identity() -> Matrix {
+        Matrix {
+            m: [[vec![1], Vec::new()], [Vec::new(), vec![1]]],
+            det: Positive,
+        }
+    }
+
+    /// Is this the identity matrix.
+    ///
+    /// Any other reduction matrix has `m10 >= 1`.
+    pub(crate) fn is_identity(&self) -> bool {
+        self.m[1][0].is_empty()
+    }
+
+    /// self * rhs
+    pub(crate) fn mul(&self, rhs: &Matrix) -> Matrix {
+        let entry = |i: usize, j: usize| {
+            helpers::add(
+                &helpers::mul(&self.m[i][0], &rhs.m[0][j]),
+                &helpers::mul(&self.m[i][1], &rhs.m[1][j]),
+            )
+        };
+        Matrix {
+            m: [[entry(0, 0), entry(0, 1)], [entry(1, 0), entry(1, 1)]],
+            det: self.det * rhs.det,
+        }
+    }
+
+    /// self * rhs
+    pub(crate) fn mul_word_matrix(&self, rhs: &WordMatrix) -> Matrix {
+        let [[r00, r01], [r10, r11]] = rhs.m;
+        let row = |i: usize| {
+            [
+                helpers::mul_add_words(r00, &self.m[i][0], r10, &self.m[i][1]),
+                helpers::mul_add_words(r01, &self.m[i][0], r11, &self.m[i][1]),
+            ]
+        };
+        Matrix {
+            m: [row(0), row(1)],
+            det: self.det * rhs.det,
+        }
+    }
+
+    /// self * [[q, 1], [1, 0]]
+    pub(crate) fn mul_quotient(&self, q: &[Word]) -> Matrix {
+        let row = |i: usize| {
+            [
+                helpers::add(&helpers::mul(&self.m[i][0], q), &self.m[i][1]),
+                self.m[i][0].clone(),
+            ]
+        };
+        Matrix {
+            m: [row(0), row(1)],
+            det: -self.det,
+        }
+    }
+
+    /// M^-1 * (a, b)
+    ///
+    /// Panics if the result is negative.
+    pub(crate) fn apply(&self, a: &[Word], b: &[Word]) -> (Vec<Word>, Vec<Word>) {
+        let [[m00, m01], [m10, m11]] = &self.m;
+        let a_pos = helpers::mul(m11, a);
+        let a_neg = helpers::mul(m01, b);
+        let b_pos = helpers::mul(m00, b);
+        let b_neg = helpers::mul(m10, a);
+        match self.det {
+            Positive => (helpers::sub(&a_pos, &a_neg), helpers::sub(&b_pos, &b_neg)),
+            Negative => (helpers::sub(&a_neg, &a_pos), helpers::sub(&b_neg, &b_pos)),
+        }
+    }
+
+    /// Whether the matrix is safe to use for numbers that `(a, b)` is the truncation of, where
+    /// `(a, b)` is the reduced pair.
+    ///
+    /// If `(A, B) = (a * 2^k + a_lo, b * 2^k + b_lo)` with `a_lo, b_lo < 2^k`, then
+    /// `M^-1 (A, B) = 2^k (a, b) + M^-1 (a_lo, b_lo)` and the error term is less than
+    /// `2^k * m00` in absolute value. So if `b >= m00` and `a - b >= 2 * m00`,
+    /// `M^-1 (A, B)` is a valid reduced pair `A' > B' > 0`.
+    pub(crate) fn is_safe_for(&self, a: &[Word], b: &[Word]) -> bool {
+        let m00 = &self.m[0][0];
+        helpers::cmp(b, m00) != Less
+            && helpers::cmp(a, b) == Greater
+            && helpers::cmp(&helpers::sub(a, b), &helpers::add(m00, m00)) != Less
+    }
+}
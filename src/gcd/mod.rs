@@ -0,0 +1,464 @@
+//! Greatest common divisor.
+
+use crate::{
+    arch::word::Word,
+    ibig::IBig,
+    ops::DivRem,
+    ubig::{Repr::*, UBig},
+};
+use alloc::vec::Vec;
+use core::{borrow::Borrow, cmp::Ordering::*, mem};
+
+mod half_gcd;
+mod helpers;
+mod lehmer;
+mod matrix;
+
+impl UBig {
+    /// Greatest common divisor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(12).gcd(&ubig!(18)), ubig!(6));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `ubig!(0).gcd(&ubig!(0))` panics.
+    pub fn gcd(&self, rhs: &UBig) -> UBig {
+        if let (Large(a), Large(b)) = (self.repr(), rhs.repr()) {
+            return UBig::gcd_large(a.to_vec(), b.to_vec());
+        }
+
+        let (mut a, mut b) = (self.clone(), rhs.clone());
+
+        let zeros = match (a.trailing_zeros(), b.trailing_zeros()) {
+            (None, None) => panic!("gcd(0, 0)"),
+            (None, Some(_)) => return b,
+            (Some(_), None) => return a,
+            (Some(a_zeros), Some(b_zeros)) => {
+                a >>= a_zeros;
+                b >>= b_zeros;
+                a_zeros.min(b_zeros)
+            }
+        };
+
+        // One round of Euclidean algorithm.
+        if a < b {
+            mem::swap(&mut a, &mut b);
+        }
+        a %= &b;
+
+        // Binary algorithm.
+        loop {
+            // b is odd
+            match a.trailing_zeros() {
+                None => break,
+                Some(a_zeros) => a >>= a_zeros,
+            }
+            // a is odd
+
+            if a < b {
+                mem::swap(&mut a, &mut b);
+            }
+            a -= &b;
+        }
+
+        b << zeros
+    }
+
+    /// Greatest common divisors and the Bézout coefficients.
+    ///
+    /// If `a.extended_gcd(&b) == (g, x, y)` then:
+    /// * `x * a + y * b == g`
+    /// * `abs(x) <= max(b, 1)`
+    /// * `abs(y) <= max(a, 1)`
+    ///
+    /// # Example
+    /// ```
+    /// # use ibig::{ubig, IBig, ops::UnsignedAbs};
+    /// let a = ubig!(12);
+    /// let b = ubig!(18);
+    /// let (g, x, y) = a.extended_gcd(&b);
+    /// assert_eq!(&a % &g, ubig!(0));
+    /// assert_eq!(&b % &g, ubig!(0));
+    /// assert_eq!(&x * IBig::from(&a) + &y * IBig::from(&b), IBig::from(g));
+    /// assert!(x.unsigned_abs() <= b);
+    /// assert!(y.unsigned_abs() <= a);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `ubig!(0).extended_gcd(&ubig!(0))` panics.
+    pub fn extended_gcd(&self, rhs: &UBig) -> (UBig, IBig, IBig) {
+        if let (Large(a), Large(b)) = (self.repr(), rhs.repr()) {
+            return UBig::extended_gcd_large(a.to_vec(), b.to_vec());
+        }
+
+        let zeros = match (self.trailing_zeros(), rhs.trailing_zeros()) {
+            (None, None) => panic!("extended_gcd(0, 0)"),
+            (None, Some(_)) => return (rhs.clone(), 0u8.into(), 1u8.into()),
+            (Some(_), None) => return (self.clone(), 1u8.into(), 0u8.into()),
+            (Some(a_zeros), Some(b_zeros)) => a_zeros.min(b_zeros),
+        };
+
+        let u = self >> zeros;
+        let v = rhs >> zeros;
+        let mut a;
+        let mut b;
+        let mut ax;
+        let mut ay;
+        let mut bx;
+        let mut by;
+
+        // Invariants:
+        // gcd(a, b) == gcd(u, v)
+        // a = ax * u - ay * v
+        // b = bx * u - by * v
+        // ax, bx <= v
+        // ay, by <= u
+
+        // One round of Euclidean algorithm.
+        if u <= v {
+            let (q, r) = (&v).div_rem(&u);
+            // u = 1 * u - 0 * v
+            // r = v - q * u = (v-q) * u - (u-1) * v
+            a = u.clone();
+            ax = UBig::from_word(1);
+            ay = UBig::from_word(0);
+            b = r;
+            bx = &v - q;
+            by = &u - UBig::from_word(1);
+        } else {
+            let (q, r) = (&u).div_rem(&v);
+            // v = 0 * u + 1 * v = v * u - (u-1) * v
+            // r = 1 * u - q * v
+            a = v.clone();
+            ax = v.clone();
+            ay = &u - UBig::from_word(1);
+
+            b = r;
+            bx = UBig::from_word(1);
+            by = q;
+        }
+
+        // At least one of a and b is odd (because gcd(u, v) is odd). Make b odd.
+        if &b & 1u8 == 0u8 {
+            mem::swap(&mut a, &mut b);
+            mem::swap(&mut ax, &mut bx);
+            mem::swap(&mut ay, &mut by);
+        }
+
+        // Binary algorithm.
+        while a != UBig::from_word(0) {
+            // b is odd
+            while &a & 1u8 == 0u8 {
+                // a is even
+                if &ax & 1u8 != 0u8 || &ay & 1u8 != 0u8 {
+                    ax += &v;
+                    ay += &u;
+                }
+                // Now ax, ay are even.
+                a >>= 1usize;
+                ax >>= 1usize;
+                ay >>= 1usize;
+                // Again ax <= v, bx <= u.
+            }
+            // Both a and b are odd.
+            if a < b {
+                mem::swap(&mut a, &mut b);
+                mem::swap(&mut ax, &mut bx);
+                mem::swap(&mut ay, &mut by);
+            }
+            a -= &b;
+            if ax < bx {
+                ax += &v;
+                ay += &u;
+            }
+            ax -= &bx;
+            ay -= &by;
+            // ax >= 0 in both cases
+            // ax <= v in both cases
+            // ax * u - ay * v = a
+            // ay * v = ax * u - a <= v * u - 0
+            // ay <= u
+            // After one round Euclidean, and at least one subtraction, a < min(u,v).
+            // ay * v = ax * u - a >= -a > -min(u,v) >= -v
+            // ay >= 0
+        }
+
+        (b << zeros, IBig::from(bx), -IBig::from(by))
+    }
+
+    /// Greatest common divisor of two large numbers.
+    ///
+    /// Uses the half-gcd algorithm for very large numbers, and Lehmer's algorithm otherwise.
+    fn gcd_large(mut a: Vec<Word>, mut b: Vec<Word>) -> UBig {
+        if helpers::cmp(&a, &b) == Less {
+            mem::swap(&mut a, &mut b);
+        }
+        while !b.is_empty() {
+            if a.len() >= half_gcd::THRESHOLD {
+                if !half_gcd::reduce(&mut a, &mut b).is_identity() {
+                    continue;
+                }
+            } else if let Some(step) = lehmer::step(&a, &b) {
+                let (new_a, new_b) = step.apply(&a, &b);
+                a = new_a;
+                b = new_b;
+                continue;
+            }
+            let _ = helpers::div_rem_in_place(&mut a, &b);
+            mem::swap(&mut a, &mut b);
+        }
+        UBig::from_words(&a)
+    }
+
+    /// Greatest common divisor and the Bézout coefficients of two large numbers.
+    fn extended_gcd_large(mut a: Vec<Word>, mut b: Vec<Word>) -> (UBig, IBig, IBig) {
+        let swapped = helpers::cmp(&a, &b) == Less;
+        if swapped {
+            mem::swap(&mut a, &mut b);
+        }
+        // (a_0, b_0) = m (a, b)
+        let mut m = matrix::Matrix::identity();
+        while !b.is_empty() {
+            if a.len() >= half_gcd::THRESHOLD {
+                let m_reduce = half_gcd::reduce(&mut a, &mut b);
+                if !m_reduce.is_identity() {
+                    m = m.mul(&m_reduce);
+                    continue;
+                }
+            } else if let Some(step) = lehmer::step(&a, &b) {
+                let (new_a, new_b) = step.apply(&a, &b);
+                a = new_a;
+                b = new_b;
+                m = m.mul_word_matrix(&step);
+                continue;
+            }
+            let q = helpers::div_rem_in_place(&mut a, &b);
+            mem::swap(&mut a, &mut b);
+            m = m.mul_quotient(&q);
+        }
+        // (a_0, b_0) = m (g, 0), so g = det * (m11 * a_0 - m01 * b_0).
+        // The entries are ordered, so |m11| <= m10 = b_0 / g and |m01| <= m00 = a_0 / g.
+        let [[_, m01], [_, m11]] = &m.m;
+        let mut x = IBig::from_sign_magnitude(m.det, UBig::from_words(m11));
+        let mut y = IBig::from_sign_magnitude(-m.det, UBig::from_words(m01));
+        if swapped {
+            mem::swap(&mut x, &mut y);
+        }
+        (UBig::from_words(&a), x, y)
+    }
+
+    /// Least common multiple.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(12).lcm(&ubig!(18)), ubig!(36));
+    /// assert_eq!(ubig!(0).lcm(&ubig!(18)), ubig!(0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is longer than [UBig::MAX_BIT_LEN].
+    pub fn lcm(&self, rhs: &UBig) -> UBig {
+        match self.checked_lcm(rhs) {
+            Some(res) => res,
+            None => UBig::panic_number_too_large(),
+        }
+    }
+
+    /// Least common multiple.
+    ///
+    /// Returns `None` if the result may be longer than [UBig::MAX_BIT_LEN].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(12).checked_lcm(&ubig!(18)), Some(ubig!(36)));
+    /// ```
+    pub fn checked_lcm(&self, rhs: &UBig) -> Option<UBig> {
+        let zero = UBig::from_word(0);
+        if *self == zero || *rhs == zero {
+            return Some(zero);
+        }
+        let a = self / self.gcd(rhs);
+        // The product has either a.bit_len() + rhs.bit_len() or one fewer bits.
+        if a.bit_len() + rhs.bit_len() > UBig::MAX_BIT_LEN {
+            None
+        } else {
+            Some(a * rhs)
+        }
+    }
+}
+
+impl IBig {
+    /// Greatest common divisor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// assert_eq!(ibig!(-12).gcd(&ibig!(18)), ibig!(6));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `ibig!(0).gcd(&ibig!(0))` panics.
+    pub fn gcd(&self, rhs: &IBig) -> IBig {
+        self.magnitude().gcd(rhs.magnitude()).into()
+    }
+
+    /// Greatest common divisors and the Bézout coefficients.
+    ///
+    /// If `a.extended_gcd(&b) == (g, x, y)` then:
+    /// * `x * a + y * b == g`
+    /// * `abs(x) <= max(abs(b), 1)`
+    /// * `abs(y) <= max(abs(a), 1)`
+    ///
+    /// # Example
+    /// ```
+    /// # use ibig::{ibig, IBig, ops::Abs};
+    /// let a = ibig!(-12);
+    /// let b = ibig!(18);
+    /// let (g, x, y) = a.extended_gcd(&b);
+    /// assert_eq!(&a % &g, ibig!(0));
+    /// assert_eq!(&b % &g, ibig!(0));
+    /// assert_eq!(&x * &a + &y * &b, g);
+    /// assert!(x.abs() <= b.abs());
+    /// assert!(y.abs() <= a.abs());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `ibig!(0).extended_gcd(&ibig!(0))` panics.
+    pub fn extended_gcd(&self, rhs: &IBig) -> (IBig, IBig, IBig) {
+        let (g, x, y) = self.magnitude().extended_gcd(rhs.magnitude());
+        (IBig::from(g), self.sign() * x, rhs.sign() * y)
+    }
+
+    /// Least common multiple.
+    ///
+    /// The result is never negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// assert_eq!(ibig!(-12).lcm(&ibig!(18)), ibig!(36));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result is longer than [UBig::MAX_BIT_LEN].
+    pub fn lcm(&self, rhs: &IBig) -> IBig {
+        self.magnitude().lcm(rhs.magnitude()).into()
+    }
+
+    /// Least common multiple.
+    ///
+    /// The result is never negative. Returns `None` if the result may be longer than
+    /// [UBig::MAX_BIT_LEN].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ibig::ibig;
+    /// assert_eq!(ibig!(-12).checked_lcm(&ibig!(-18)), Some(ibig!(36)));
+    /// ```
+    pub fn checked_lcm(&self, rhs: &IBig) -> Option<IBig> {
+        self.magnitude()
+            .checked_lcm(rhs.magnitude())
+            .map(IBig::from)
+    }
+}
+
+/// Greatest common divisor of many numbers.
+///
+/// Zeros are ignored. Returns 0 if there are no non-zero numbers.
+///
+/// Stops consuming the iterator as soon as the result is 1.
+///
+/// # Example
+///
+/// ```
+/// # use ibig::{gcd_all, ubig};
+/// assert_eq!(gcd_all(&[ubig!(12), ubig!(0), ubig!(18), ubig!(27)]), ubig!(3));
+/// assert_eq!(gcd_all(Vec::<ibig::UBig>::new()), ubig!(0));
+/// ```
+pub fn gcd_all<I>(numbers: I) -> UBig
+where
+    I: IntoIterator,
+    I::Item: Borrow<UBig>,
+{
+    let zero = UBig::from_word(0);
+    let one = UBig::from_word(1);
+    let mut g = zero.clone();
+    for x in numbers {
+        let x = x.borrow();
+        if *x == zero {
+            continue;
+        }
+        g = if g == zero { x.clone() } else { g.gcd(x) };
+        if g == one {
+            break;
+        }
+    }
+    g
+}
+
+/// Greatest common divisor of many numbers and the Bézout coefficients.
+///
+/// If `extended_gcd_all(a) == (g, x)` then `x.len() == a.len()` and
+/// `x[0] * a[0] + x[1] * a[1] + ... == g`.
+///
+/// Zeros are ignored and get coefficient 0. Returns 0 if there are no non-zero numbers.
+///
+/// # Example
+///
+/// ```
+/// # use ibig::{extended_gcd_all, ubig, IBig};
+/// let a = [ubig!(12), ubig!(18), ubig!(27)];
+/// let (g, x) = extended_gcd_all(&a);
+/// assert_eq!(g, ubig!(3));
+/// assert_eq!(x.len(), 3);
+/// assert_eq!(
+///     &x[0] * IBig::from(&a[0]) + &x[1] * IBig::from(&a[1]) + &x[2] * IBig::from(&a[2]),
+///     IBig::from(g)
+/// );
+/// ```
+pub fn extended_gcd_all<I>(numbers: I) -> (UBig, Vec<IBig>)
+where
+    I: IntoIterator,
+    I::Item: Borrow<UBig>,
+{
+    let zero = UBig::from_word(0);
+    let mut g = zero.clone();
+    let mut coefficients: Vec<IBig> = Vec::new();
+    for x in numbers {
+        let x = x.borrow();
+        if *x == zero {
+            coefficients.push(IBig::from(0u8));
+        } else if g == zero {
+            g = x.clone();
+            coefficients.push(IBig::from(1u8));
+        } else {
+            // new_g = s * g + t * x
+            let (new_g, s, t) = g.extended_gcd(x);
+            if s != IBig::from(1u8) {
+                for c in coefficients.iter_mut() {
+                    *c *= &s;
+                }
+            }
+            coefficients.push(t);
+            g = new_g;
+        }
+    }
+    (g, coefficients)
+}
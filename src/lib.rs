@@ -21,6 +21,15 @@
 //!
 //! Modular arithmetic is supported by the module [modular].
 //!
+//! Integer factorization is supported by the module [factor].
+//!
+//! Number-theoretic transforms and convolutions modulo NTT-friendly primes are provided by the
+//! module [ntt].
+//!
+//! Factorials and binomial coefficients are computed by [factorial], [double_factorial],
+//! [binomial] and [multinomial], and Fibonacci and Lucas numbers by [fibonacci], [lucas],
+//! [fibonacci_pair] and [fibonacci_mod].
+//!
 //! # Examples
 //!
 //! ```
@@ -58,13 +67,22 @@
 //! * `num-traits` (default): integral traits.
 //! * `rand` (default): random number generation.
 //! * `serde`: serialization and deserialization.
+//! * `parallel`: multithreaded multiplication and radix conversion of very large numbers,
+//!   using `rayon`. Requires the Rust version that `rayon` requires, newer than 1.59.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
 
 pub use crate::memory::Stack;
-pub use crate::{ibig::IBig, ubig::UBig};
+pub use crate::{
+    divisor::Divisor,
+    factorial::{binomial, double_factorial, factorial, multinomial},
+    fibonacci::{fibonacci, fibonacci_mod, fibonacci_pair, lucas},
+    gcd::{extended_gcd_all, gcd_all},
+    ibig::IBig,
+    ubig::UBig,
+};
 
 mod add;
 mod add_ops;
@@ -76,8 +94,12 @@ mod cmp;
 mod convert;
 mod div;
 mod div_ops;
+mod divisor;
 pub mod error;
+pub mod factor;
+mod factorial;
 mod fast_divide;
+mod fibonacci;
 pub mod fmt;
 mod gcd;
 mod helper_macros;
@@ -88,11 +110,13 @@ mod memory;
 pub mod modular;
 mod mul;
 mod mul_ops;
+pub mod ntt;
 pub mod ops;
 mod parse;
 mod pow;
 mod primitive;
 mod radix;
+mod reciprocal;
 mod shift;
 mod shift_ops;
 mod sign;
@@ -106,3 +130,6 @@ mod num_traits;
 
 #[cfg(feature = "serde")]
 mod serde;
+
+#[cfg(feature = "parallel")]
+mod parallel;
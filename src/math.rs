@@ -87,15 +87,6 @@ pub(crate) const fn ones_word(n: u32) -> Word {
     }
 }
 
-#[inline]
-pub(crate) const fn min_usize(a: usize, b: usize) -> usize {
-    if a < b {
-        a
-    } else {
-        b
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
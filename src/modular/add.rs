@@ -164,7 +164,7 @@ impl<'a> SubAssign<&Modulo<'a>> for Modulo<'a> {
 impl ModuloSmallRaw {
     /// -self
     #[inline]
-    fn negate(self, ring: &ModuloRingSmall) -> ModuloSmallRaw {
+    pub(crate) fn negate(self, ring: &ModuloRingSmall) -> ModuloSmallRaw {
         debug_assert!(self.is_valid(ring));
         let normalized_val = match self.normalized() {
             0 => 0,
@@ -175,7 +175,7 @@ impl ModuloSmallRaw {
 
     /// self + other
     #[inline]
-    fn add(self, other: ModuloSmallRaw, ring: &ModuloRingSmall) -> ModuloSmallRaw {
+    pub(crate) fn add(self, other: ModuloSmallRaw, ring: &ModuloRingSmall) -> ModuloSmallRaw {
         debug_assert!(self.is_valid(ring) && other.is_valid(ring));
         let (mut val, overflow) = self.normalized().overflowing_add(other.normalized());
         let m = ring.normalized_modulus();
@@ -189,7 +189,7 @@ impl ModuloSmallRaw {
 
     /// self - other
     #[inline]
-    fn sub(self, other: ModuloSmallRaw, ring: &ModuloRingSmall) -> ModuloSmallRaw {
+    pub(crate) fn sub(self, other: ModuloSmallRaw, ring: &ModuloRingSmall) -> ModuloSmallRaw {
         debug_assert!(self.is_valid(ring) && other.is_valid(ring));
         let (mut val, overflow) = self.normalized().overflowing_sub(other.normalized());
         if overflow {
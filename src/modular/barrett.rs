@@ -0,0 +1,99 @@
+//! Constant-time Barrett reduction, for moduli without a Montgomery representation.
+//!
+//! For a normalized modulus `M` of `n` words let `b = 2^WORD_BITS` and `mu = b^(2n) / M`. For
+//! `x < b^(2n)`, the quotient estimate `q = (x / b^(n-1)) * mu / b^(n+1)` is at most 2 less than
+//! `x / M`, so `x - q M` is reduced by at most two subtractions of `M` (HAC 14.42).
+
+use crate::{
+    arch::{self, word::Word},
+    modular::montgomery::{add_mul_word_ct, sub_if_not_less_ct},
+    primitive::WORD_BITS_USIZE,
+    shift,
+    ubig::UBig,
+};
+use alloc::vec::Vec;
+
+/// Parameters of Barrett reduction.
+pub(crate) struct Barrett {
+    /// The modulus, normalized: the top bit is set.
+    normalized_modulus: Vec<Word>,
+    shift: u32,
+    /// `b^(2n) / M`, `n + 1` words.
+    mu: Vec<Word>,
+}
+
+impl Barrett {
+    /// Barrett parameters for a modulus with normalized form `normalized_modulus`.
+    pub(crate) fn new(normalized_modulus: &[Word], shift: u32) -> Barrett {
+        let n = normalized_modulus.len();
+        let mu = (UBig::from_word(1) << (2 * n * WORD_BITS_USIZE))
+            / UBig::from_words(normalized_modulus);
+        let mut mu = mu.as_words().to_vec();
+        debug_assert!(mu.len() == n + 1);
+        mu.resize(n + 1, 0);
+        Barrett {
+            normalized_modulus: normalized_modulus.to_vec(),
+            shift,
+            mu,
+        }
+    }
+
+    /// Temporary words required for `mul_ct`.
+    pub(crate) fn mul_ct_len(&self) -> usize {
+        5 * self.normalized_modulus.len() + 3
+    }
+
+    /// Modular product `a * b mod m` in constant time.
+    ///
+    /// `a` and `b` are not normalized and less than `m`. The result is stored in `t[..n]`, where
+    /// `t` has `mul_ct_len()` words.
+    ///
+    /// The products are schoolbook and the final corrections are masked, so the running time
+    /// only depends on `n`.
+    pub(crate) fn mul_ct(&self, a: &[Word], b: &[Word], t: &mut [Word]) {
+        let modulus = &self.normalized_modulus[..];
+        let n = modulus.len();
+        debug_assert!(a.len() == n && b.len() == n && t.len() == self.mul_ct_len());
+        let (x, t) = t.split_at_mut(2 * n);
+        let (q2, r2) = t.split_at_mut(2 * n + 2);
+
+        // x = a * b * 2^shift < M^2 / 2^shift <= b^(2n)
+        x.fill(0);
+        for (i, &a_word) in a.iter().enumerate() {
+            x[i + n] = add_mul_word_ct(&mut x[i..i + n], a_word, b);
+        }
+        let carry = shift::shl_in_place(x, self.shift);
+        debug_assert!(carry == 0);
+
+        // q2 = (x / b^(n-1)) * mu
+        let q1 = &x[n - 1..];
+        q2.fill(0);
+        for (i, &q1_word) in q1.iter().enumerate() {
+            q2[i + n + 1] = add_mul_word_ct(&mut q2[i..i + n + 1], q1_word, &self.mu);
+        }
+
+        // r2 = q3 * M mod b^(n+1), where q3 = q2 / b^(n+1)
+        let q3 = &q2[n + 1..];
+        r2.fill(0);
+        for (i, &q3_word) in q3.iter().enumerate() {
+            let len = (n + 1 - i).min(n);
+            let carry = add_mul_word_ct(&mut r2[i..i + len], q3_word, &modulus[..len]);
+            if i == 0 {
+                r2[n] = carry;
+            }
+        }
+
+        // x - q3 * M = (x - r2) mod b^(n+1) < 3M
+        let r = &mut x[..n + 1];
+        let mut borrow = false;
+        for (r_word, &r2_word) in r.iter_mut().zip(r2.iter()) {
+            let (diff, b) = arch::add::sub_with_borrow(*r_word, r2_word, borrow);
+            *r_word = diff;
+            borrow = b;
+        }
+        sub_if_not_less_ct(r, modulus);
+        sub_if_not_less_ct(r, modulus);
+        debug_assert!(r[n] == 0);
+        shift::shr_in_place(&mut r[..n], self.shift);
+    }
+}
@@ -0,0 +1,232 @@
+//! Modular arithmetic with a modulus known at compile time.
+
+use crate::{
+    arch::word::Word,
+    assert::assert_in_const_fn,
+    ibig::IBig,
+    modular::{
+        modulo::{ModuloSmall, ModuloSmallRaw},
+        modulo_ring::ModuloRingSmall,
+    },
+    primitive::WORD_BITS,
+    sign::Sign::*,
+    ubig::UBig,
+};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Modular arithmetic with a word-sized modulus `M` known at compile time.
+///
+/// The ring is computed at compile time, so a value is a single word with no reference to a
+/// ring. It is `Copy`, and a `Vec` of values takes no more space than a `Vec<u64>`.
+///
+/// `M` must be nonzero and fit in a machine word; otherwise using the type fails to compile.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{modular::ConstModulo, ubig};
+/// type F = ConstModulo<65521>;
+/// let x = F::new(3);
+/// assert_eq!(x.pow(&ubig!(65520)), F::new(1));
+/// assert_eq!((F::new(2) - F::new(5)).residue(), 65518);
+/// ```
+///
+/// ```compile_fail
+/// # use ibig::modular::ConstModulo;
+/// let x = ConstModulo::<0>::new(1);
+/// ```
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct ConstModulo<const M: u64> {
+    raw: ModuloSmallRaw,
+}
+
+impl<const M: u64> ConstModulo<M> {
+    /// The ring of integers modulo `M`.
+    const RING: ModuloRingSmall = {
+        assert_in_const_fn(M != 0 && (WORD_BITS >= 64 || M >> WORD_BITS == 0));
+        ModuloRingSmall::new(M as Word)
+    };
+
+    /// The ring of integers modulo `M`.
+    #[inline]
+    pub(crate) const fn ring() -> &'static ModuloRingSmall {
+        &Self::RING
+    }
+
+    /// Convert `x` to a value modulo `M`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::modular::ConstModulo;
+    /// const X: ConstModulo<100> = ConstModulo::new(1234);
+    /// assert_eq!(X.residue(), 34);
+    /// ```
+    #[inline]
+    pub const fn new(x: u64) -> ConstModulo<M> {
+        ConstModulo {
+            raw: ModuloSmallRaw::from_word((x % M) as Word, Self::ring()),
+        }
+    }
+
+    /// The modulus `M`.
+    #[inline]
+    pub const fn modulus() -> u64 {
+        M
+    }
+
+    /// Get the residue in range `0..M`.
+    #[inline]
+    #[allow(clippy::useless_conversion)]
+    pub fn residue(self) -> u64 {
+        u64::from(self.raw.residue(Self::ring()))
+    }
+
+    /// Exponentiation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::ConstModulo, ubig};
+    /// let x = ConstModulo::<100>::new(13);
+    /// assert_eq!(x.pow(&ubig!(2)).residue(), 69);
+    /// ```
+    #[inline]
+    pub fn pow(self, exp: &UBig) -> ConstModulo<M> {
+        ConstModulo {
+            raw: self.to_small().pow(exp).raw(),
+        }
+    }
+
+    /// Inverse.
+    ///
+    /// Returns `None` if there is no unique inverse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::modular::ConstModulo;
+    /// type F = ConstModulo<10>;
+    /// assert_eq!(F::new(7).inverse(), Some(F::new(3)));
+    /// assert_eq!(F::new(2).inverse(), None);
+    /// ```
+    #[inline]
+    pub fn inverse(self) -> Option<ConstModulo<M>> {
+        let inv = self.to_small().inverse()?;
+        Some(ConstModulo { raw: inv.raw() })
+    }
+
+    /// The same value attached to the ring.
+    #[inline]
+    pub(crate) fn to_small(self) -> ModuloSmall<'static> {
+        ModuloSmall::new(self.raw, Self::ring())
+    }
+}
+
+impl<const M: u64> From<&UBig> for ConstModulo<M> {
+    #[inline]
+    fn from(x: &UBig) -> ConstModulo<M> {
+        ConstModulo {
+            raw: ModuloSmall::from_ubig(x, Self::ring()).raw(),
+        }
+    }
+}
+
+impl<const M: u64> From<UBig> for ConstModulo<M> {
+    #[inline]
+    fn from(x: UBig) -> ConstModulo<M> {
+        ConstModulo::from(&x)
+    }
+}
+
+impl<const M: u64> From<&IBig> for ConstModulo<M> {
+    #[inline]
+    fn from(x: &IBig) -> ConstModulo<M> {
+        let modulo = ConstModulo::from(x.magnitude());
+        match x.sign() {
+            Positive => modulo,
+            Negative => -modulo,
+        }
+    }
+}
+
+impl<const M: u64> From<IBig> for ConstModulo<M> {
+    #[inline]
+    fn from(x: IBig) -> ConstModulo<M> {
+        ConstModulo::from(&x)
+    }
+}
+
+impl<const M: u64> Neg for ConstModulo<M> {
+    type Output = ConstModulo<M>;
+
+    #[inline]
+    fn neg(self) -> ConstModulo<M> {
+        ConstModulo {
+            raw: self.raw.negate(Self::ring()),
+        }
+    }
+}
+
+impl<const M: u64> Add for ConstModulo<M> {
+    type Output = ConstModulo<M>;
+
+    #[inline]
+    fn add(self, rhs: ConstModulo<M>) -> ConstModulo<M> {
+        ConstModulo {
+            raw: self.raw.add(rhs.raw, Self::ring()),
+        }
+    }
+}
+
+impl<const M: u64> Sub for ConstModulo<M> {
+    type Output = ConstModulo<M>;
+
+    #[inline]
+    fn sub(self, rhs: ConstModulo<M>) -> ConstModulo<M> {
+        ConstModulo {
+            raw: self.raw.sub(rhs.raw, Self::ring()),
+        }
+    }
+}
+
+impl<const M: u64> Mul for ConstModulo<M> {
+    type Output = ConstModulo<M>;
+
+    #[inline]
+    fn mul(self, rhs: ConstModulo<M>) -> ConstModulo<M> {
+        ConstModulo {
+            raw: self.raw.mul(rhs.raw, Self::ring()),
+        }
+    }
+}
+
+impl<const M: u64> Div for ConstModulo<M> {
+    type Output = ConstModulo<M>;
+
+    #[inline]
+    fn div(self, rhs: ConstModulo<M>) -> ConstModulo<M> {
+        // Clippy doesn't like that division is implemented using multiplication.
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        match rhs.inverse() {
+            None => panic!("Division by a non-invertible ConstModulo"),
+            Some(inv_rhs) => self * inv_rhs,
+        }
+    }
+}
+
+macro_rules! impl_assign {
+    ($tr:ident, $f:ident, $op:tt) => {
+        impl<const M: u64> $tr for ConstModulo<M> {
+            #[inline]
+            fn $f(&mut self, rhs: ConstModulo<M>) {
+                *self = *self $op rhs;
+            }
+        }
+    };
+}
+
+impl_assign!(AddAssign, add_assign, +);
+impl_assign!(SubAssign, sub_assign, -);
+impl_assign!(MulAssign, mul_assign, *);
+impl_assign!(DivAssign, div_assign, /);
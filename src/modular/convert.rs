@@ -51,6 +51,41 @@ impl ModuloRing {
     pub fn from<T: IntoModulo>(&self, x: T) -> Modulo {
         x.into_modulo(self)
     }
+
+    /// Create an element of the ring from its centered residue, in range `(-n/2, n/2]` in an
+    /// n-element ring.
+    ///
+    /// This is the inverse of [Modulo::centered_residue].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, modular::ModuloRing, ubig};
+    /// let ring = ModuloRing::new(&ubig!(100));
+    /// let x = ring.from_centered(&ibig!(-3));
+    /// assert_eq!(x, ring.from(97));
+    /// assert_eq!(x.centered_residue(), ibig!(-3));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is out of range.
+    #[inline]
+    pub fn from_centered(&self, x: &IBig) -> Modulo<'_> {
+        self.from(Centered(x))
+    }
+
+    /// Panics if `x` is not in range `(-n/2, n/2]`.
+    fn check_centered(&self, x: &IBig) {
+        let modulus = self.modulus();
+        let in_range = match x.sign() {
+            Positive => *x.magnitude() <= modulus >> 1,
+            Negative => x.magnitude() << 1 < modulus,
+        };
+        if !in_range {
+            panic!("Centered residue out of range")
+        }
+    }
 }
 
 impl ModuloRingSmall {
@@ -89,6 +124,26 @@ impl Modulo<'_> {
             ModuloRepr::Large(self_large) => self_large.residue(),
         }
     }
+
+    /// Get the centered residue in range `(-n/2, n/2]` in an n-element ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, modular::ModuloRing, ubig};
+    /// let ring = ModuloRing::new(&ubig!(100));
+    /// assert_eq!(ring.from(-1234).centered_residue(), ibig!(-34));
+    /// assert_eq!(ring.from(50).centered_residue(), ibig!(50));
+    /// assert_eq!(ring.from(51).centered_residue(), ibig!(-49));
+    /// ```
+    #[inline]
+    pub fn centered_residue(&self) -> IBig {
+        let modulus = match self.repr() {
+            ModuloRepr::Small(self_small) => UBig::from_word(self_small.ring().modulus()),
+            ModuloRepr::Large(self_large) => self_large.ring().modulus(),
+        };
+        IBig::rem_centered_from_truncated(IBig::from(self.residue()), &modulus)
+    }
 }
 
 impl ModuloSmallRaw {
@@ -127,11 +182,29 @@ impl ModuloSmall<'_> {
 impl ModuloLarge<'_> {
     pub(crate) fn residue(&self) -> UBig {
         let words = self.normalized_value();
-        let mut buffer = Buffer::allocate(words.len());
-        buffer.extend(words);
-        let low_bits = shift::shr_in_place(&mut buffer, self.ring().shift());
-        assert!(low_bits == 0);
-        buffer.into()
+        let ring = self.ring();
+        match ring.montgomery() {
+            None => {
+                let mut buffer = Buffer::allocate(words.len());
+                buffer.extend(words);
+                let low_bits = shift::shr_in_place(&mut buffer, ring.shift());
+                assert!(low_bits == 0);
+                buffer.into()
+            }
+            Some(montgomery) => {
+                // x = (x * R) / R
+                let n = words.len();
+                let mut buffer = Buffer::allocate(2 * n);
+                buffer.extend(words);
+                let low_bits = shift::shr_in_place(&mut buffer, ring.shift());
+                assert!(low_bits == 0);
+                buffer.push_zeros(n);
+                let mut allocation = MemoryAllocation::new(montgomery.redc_memory_requirement());
+                montgomery.redc(&mut buffer, &mut allocation.memory());
+                buffer.erase_front(n);
+                buffer.into()
+            }
+        }
     }
 }
 
@@ -185,6 +258,39 @@ impl IntoModulo for &IBig {
     }
 }
 
+/// A signed integer to be converted into [Modulo] as a centered residue.
+///
+/// Converting with [IntoModulo] checks that the value is in range `(-n/2, n/2]` in an n-element
+/// ring, and panics otherwise. [ModuloRing::from_centered] does the same.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{ibig, modular::{Centered, ModuloOwned, ModuloRing}, ubig};
+/// # use std::rc::Rc;
+/// let ring = Rc::new(ModuloRing::new(&ubig!(7)));
+/// let x = ModuloOwned::new(ring, Centered(ibig!(-3)));
+/// assert_eq!(x.residue(), ubig!(4));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Centered<T>(pub T);
+
+impl IntoModulo for Centered<IBig> {
+    #[inline]
+    fn into_modulo(self, ring: &ModuloRing) -> Modulo<'_> {
+        ring.check_centered(&self.0);
+        self.0.into_modulo(ring)
+    }
+}
+
+impl IntoModulo for Centered<&IBig> {
+    #[inline]
+    fn into_modulo(self, ring: &ModuloRing) -> Modulo<'_> {
+        ring.check_centered(self.0);
+        self.0.into_modulo(ring)
+    }
+}
+
 impl<'a> ModuloSmall<'a> {
     #[inline]
     pub(crate) fn from_ubig(x: &UBig, ring: &'a ModuloRingSmall) -> ModuloSmall<'a> {
@@ -223,7 +329,13 @@ impl<'a> ModuloLarge<'a> {
             }
         }
         vec.extend(iter::repeat(0).take(modulus.len() - vec.len()));
-        ModuloLarge::new(vec, ring)
+        let mut res = ModuloLarge::new(vec, ring);
+        if let Some(montgomery) = ring.montgomery() {
+            // x * R = (x * R^2) / R
+            let mut allocation = MemoryAllocation::new(ring.mul_memory_requirement());
+            res.mul_normalized_in_place(montgomery.normalized_r2(), &mut allocation.memory());
+        }
+        res
     }
 }
 
@@ -0,0 +1,88 @@
+//! Exponentiation using the factorization of the modulus.
+
+use crate::{
+    factor,
+    modular::{modulo::ModuloLarge, modulo_ring::ModuloRing},
+    ubig::UBig,
+};
+use alloc::vec::Vec;
+
+/// Rings modulo the prime power factors of a modulus, for the Chinese remainder theorem.
+pub(crate) struct Crt {
+    factors: Vec<CrtFactor>,
+}
+
+/// A prime power factor `p^k` of the modulus `n`.
+struct CrtFactor {
+    /// The ring modulo `p^k`.
+    ring: ModuloRing,
+    /// `k`
+    exponent: usize,
+    /// `phi(p^k) = (p - 1) p^(k-1)`
+    totient: UBig,
+    /// `1 (mod p^k)` and `0` modulo the other factors.
+    coefficient: UBig,
+}
+
+impl Crt {
+    /// Prime power factors `(p, k)` and their product.
+    ///
+    /// Panics if some `p` is not prime, some `k` is zero, or some `p` is repeated.
+    pub(crate) fn new(factors: &[(UBig, usize)]) -> (UBig, Crt) {
+        let prime_powers: Vec<UBig> = factors
+            .iter()
+            .map(|(p, k)| {
+                if *k == 0 || !factor::is_probable_prime(p) {
+                    panic!("Invalid factorization: {}^{}", p, k);
+                }
+                p.pow(*k)
+            })
+            .collect();
+        let n = prime_powers
+            .iter()
+            .fold(UBig::from_word(1), |acc, q| acc * q);
+
+        let factors = factors
+            .iter()
+            .zip(prime_powers)
+            .map(|((p, k), q)| {
+                let ring = ModuloRing::new(&q);
+                let cofactor = &n / &q;
+                let coefficient = match ring.from(&cofactor).inverse() {
+                    // Repeated primes share a factor with the cofactor.
+                    None => panic!("Invalid factorization: repeated factor {}", p),
+                    Some(inv) => cofactor * inv.residue(),
+                };
+                let totient = (p - UBig::from_word(1)) * p.pow(k - 1);
+                CrtFactor {
+                    ring,
+                    exponent: *k,
+                    totient,
+                    coefficient,
+                }
+            })
+            .collect();
+        (n, Crt { factors })
+    }
+
+    /// `base^exp`, computed modulo each prime power and combined.
+    ///
+    /// Modulo `p^k` the exponent is reduced by `phi(p^k)`, keeping it at least `k` so that
+    /// bases divisible by `p` still give zero: `a^e = a^(k + (e - k) mod phi(p^k)) (mod p^k)`
+    /// for all `a` when `e >= k`.
+    pub(crate) fn pow<'a>(&self, base: &ModuloLarge<'a>, exp: &UBig) -> ModuloLarge<'a> {
+        let residue = base.residue();
+        let mut res = UBig::from_word(0);
+        for factor in &self.factors {
+            let k = UBig::from(factor.exponent);
+            let reduced_exp = if *exp < k {
+                exp.clone()
+            } else {
+                (exp - &k) % &factor.totient + k
+            };
+            let power = factor.ring.from(&residue).pow(&reduced_exp);
+            res += power.residue() * &factor.coefficient;
+        }
+        ModuloLarge::from_ubig(res, base.ring())
+    }
+}
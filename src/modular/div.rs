@@ -1,10 +1,14 @@
 use crate::{
     arch::word::Word,
     ibig::IBig,
-    modular::modulo::{Modulo, ModuloLarge, ModuloRepr, ModuloSmall, ModuloSmallRaw},
+    modular::{
+        modulo::{Modulo, ModuloLarge, ModuloRepr, ModuloSmall, ModuloSmallRaw},
+        modulo_ring::ModuloRing,
+    },
     ops::RemEuclid,
     ubig::UBig,
 };
+use alloc::vec::Vec;
 use core::{
     convert::TryInto,
     ops::{Div, DivAssign},
@@ -31,6 +35,82 @@ impl<'a> Modulo<'a> {
     }
 }
 
+impl ModuloRing {
+    /// Invert all values at once.
+    ///
+    /// Uses Montgomery's trick: one inverse and `3 * (n - 1)` multiplications for `n` values.
+    ///
+    /// If some value is not invertible, returns the index of such a value and leaves `values`
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::ModuloRing, ubig};
+    /// let ring = ModuloRing::new(&ubig!(10));
+    /// let mut values = [ring.from(3), ring.from(7), ring.from(9)];
+    /// assert_eq!(ring.batch_inverse(&mut values), Ok(()));
+    /// assert_eq!(values, [ring.from(7), ring.from(3), ring.from(9)]);
+    ///
+    /// let mut values = [ring.from(3), ring.from(4), ring.from(9)];
+    /// assert_eq!(ring.batch_inverse(&mut values), Err(1));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if some value is not from this ring.
+    pub fn batch_inverse(&self, values: &mut [Modulo]) -> Result<(), usize> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        for value in values.iter() {
+            value.check_ring(self);
+        }
+
+        // prefix[i] = values[0] * ... * values[i]
+        let mut prefix = Vec::with_capacity(values.len());
+        prefix.push(values[0].clone());
+        for (i, value) in values.iter().enumerate().skip(1) {
+            let product = &prefix[i - 1] * value;
+            prefix.push(product);
+        }
+
+        let mut inv = match prefix[values.len() - 1].inverse() {
+            Some(inv) => inv,
+            None => return Err(Self::first_non_invertible(&prefix)),
+        };
+
+        // inv = (values[0] * ... * values[i])^-1
+        for i in (1..values.len()).rev() {
+            let inv_value = &inv * &prefix[i - 1];
+            inv *= &values[i];
+            values[i] = inv_value;
+        }
+        values[0] = inv;
+        Ok(())
+    }
+
+    /// The first index at which the prefix products stop being invertible.
+    ///
+    /// Once a prefix product is not invertible, neither are the longer ones. So the first
+    /// non-invertible prefix product ends with a non-invertible value.
+    fn first_non_invertible(prefix: &[Modulo]) -> usize {
+        debug_assert!(prefix.last().unwrap().inverse().is_none());
+        // prefix[..lo] are invertible, prefix[hi] is not.
+        let mut lo = 0;
+        let mut hi = prefix.len() - 1;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if prefix[mid].inverse().is_some() {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        hi
+    }
+}
+
 impl<'a> Div<Modulo<'a>> for Modulo<'a> {
     type Output = Modulo<'a>;
 
@@ -88,7 +168,7 @@ impl<'a> DivAssign<&Modulo<'a>> for Modulo<'a> {
 
 impl<'a> ModuloSmall<'a> {
     /// Inverse.
-    fn inverse(&self) -> Option<ModuloSmall<'a>> {
+    pub(crate) fn inverse(&self) -> Option<ModuloSmall<'a>> {
         let a = self.residue();
         let b = self.ring().modulus();
         // TODO: Optimized `extended_gcd` for `Word`s.
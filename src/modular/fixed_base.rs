@@ -0,0 +1,98 @@
+//! Exponentiation of a fixed base.
+
+use crate::{modular::modulo::Modulo, ubig::UBig};
+use alloc::vec::Vec;
+
+/// Precomputed powers of a fixed base, for raising it to many exponents.
+///
+/// Uses the comb method of Lim and Lee. An exponent of up to `max_exp_bits` bits is split into
+/// `teeth` blocks of `spacing` bits. The table holds every product of the powers
+/// `base^(2^(j * spacing))` for `j < teeth`. An exponentiation then takes `spacing` squarings
+/// and at most `spacing` multiplications, compared to `max_exp_bits` squarings for
+/// [Modulo::pow].
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{modular::{FixedBasePow, ModuloRing}, ubig};
+/// let ring = ModuloRing::new(&ubig!(1000000007));
+/// let g = ring.from(5);
+/// let g_pow = FixedBasePow::new(&g, 64);
+/// for e in [ubig!(0), ubig!(12345), ubig!(0xffffffffffffffff)] {
+///     assert_eq!(g_pow.pow(&e), g.pow(&e));
+/// }
+/// ```
+pub struct FixedBasePow<'a> {
+    base: Modulo<'a>,
+    /// Number of blocks the exponent is split into.
+    teeth: u32,
+    /// Number of bits in each block.
+    spacing: usize,
+    /// `table[v]` is the product of `base^(2^(j * spacing))` over bits `j` of `v`.
+    table: Vec<Modulo<'a>>,
+}
+
+impl<'a> FixedBasePow<'a> {
+    /// Precompute powers of `base` for exponents of up to `max_exp_bits` bits.
+    ///
+    /// Larger exponents are still allowed, but they fall back to [Modulo::pow].
+    pub fn new(base: &Modulo<'a>, max_exp_bits: usize) -> FixedBasePow<'a> {
+        let teeth = FixedBasePow::choose_teeth(max_exp_bits);
+        let spacing = ((max_exp_bits + teeth as usize - 1) / teeth as usize).max(1);
+
+        let mut table = Vec::with_capacity(1 << teeth);
+        table.push(base.pow(&UBig::from_word(0)));
+        // table[2^j] = base^(2^(j * spacing))
+        let mut tooth = base.clone();
+        for j in 0..teeth {
+            if j != 0 {
+                for _ in 0..spacing {
+                    tooth.square_in_place();
+                }
+            }
+            for v in 0..1 << j {
+                let entry = if v == 0 {
+                    tooth.clone()
+                } else {
+                    &table[v] * &tooth
+                };
+                table.push(entry);
+            }
+        }
+
+        FixedBasePow {
+            base: base.clone(),
+            teeth,
+            spacing,
+            table,
+        }
+    }
+
+    /// Raise the base to the power `exp`.
+    pub fn pow(&self, exp: &UBig) -> Modulo<'a> {
+        if exp.bit_len() > self.teeth as usize * self.spacing {
+            return self.base.pow(exp);
+        }
+
+        let mut res = self.table[0].clone();
+        for i in (0..self.spacing).rev() {
+            res.square_in_place();
+            let v = (0..self.teeth as usize)
+                .rev()
+                .fold(0, |v, j| 2 * v + exp.bit(i + j * self.spacing) as usize);
+            if v != 0 {
+                res *= &self.table[v];
+            }
+        }
+        res
+    }
+
+    /// The number of teeth for exponents of `max_exp_bits` bits.
+    ///
+    /// The table has `2^teeth` entries. Keep it no larger than about a quarter of the exponent
+    /// length in bits, and at most `2^10` entries.
+    fn choose_teeth(max_exp_bits: usize) -> u32 {
+        let limit = (max_exp_bits / 4).max(2);
+        (usize::BITS - 1 - limit.leading_zeros()).min(10)
+    }
+}
@@ -1,10 +1,19 @@
-//! Formatting modular rings and modular numbers.
+//! Formatting modular rings, modular numbers and polynomials.
 
-use crate::modular::{
-    modulo::{Modulo, ModuloLarge, ModuloRepr, ModuloSmall},
-    modulo_ring::{ModuloRing, ModuloRingLarge, ModuloRingRepr, ModuloRingSmall},
+use crate::{
+    modular::{
+        const_modulo::ConstModulo,
+        modulo::{Modulo, ModuloLarge, ModuloRepr, ModuloSmall},
+        modulo_ring::{ModuloRing, ModuloRingLarge, ModuloRingRepr, ModuloRingSmall},
+        owned::ModuloOwned,
+        poly::ModPoly,
+    },
+    ubig::UBig,
+};
+use core::{
+    fmt::{self, Binary, Debug, Display, Formatter, LowerHex, Octal, UpperHex},
+    ops::Deref,
 };
-use core::fmt::{self, Binary, Debug, Display, Formatter, LowerHex, Octal, UpperHex};
 
 macro_rules! impl_fmt {
     ($t:ident) => {
@@ -40,6 +49,46 @@ macro_rules! impl_fmt {
             }
         }
 
+        impl<R: Deref<Target = ModuloRing>> $t for ModuloOwned<R> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                $t::fmt(&self.to_modulo(), f)
+            }
+        }
+
+        impl<const M: u64> $t for ConstModulo<M> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                $t::fmt(&self.to_small(), f)
+            }
+        }
+
+        /// Nonzero terms from the highest power, such as `3*x^2 + 1 (mod 7)`.
+        impl $t for ModPoly<'_> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                if self.is_zero() {
+                    $t::fmt(&UBig::from_word(0), f)?;
+                }
+                let mut first = true;
+                for (i, c) in self.coeffs().iter().enumerate().rev() {
+                    if c.is_zero() {
+                        continue;
+                    }
+                    if !first {
+                        f.write_str(" + ")?;
+                    }
+                    first = false;
+                    $t::fmt(&c.residue(), f)?;
+                    match i {
+                        0 => {}
+                        1 => f.write_str("*x")?,
+                        _ => write!(f, "*x^{}", i)?,
+                    }
+                }
+                f.write_str(" (")?;
+                $t::fmt(self.ring(), f)?;
+                f.write_str(")")
+            }
+        }
+
         impl $t for ModuloSmall<'_> {
             fn fmt(&self, f: &mut Formatter) -> fmt::Result {
                 $t::fmt(&self.residue(), f)?;
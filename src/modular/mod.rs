@@ -1,6 +1,9 @@
 //! Modular arithmetic.
 //!
 //! Modular arithmetic is performed on [Modulo] values attached to a [ModuloRing].
+//! [ModuloOwned] values hold their ring by a handle such as `Rc<ModuloRing>` or
+//! `Arc<ModuloRing>` instead of borrowing it. [ConstModulo] values have a word-sized modulus
+//! fixed at compile time. [ModPoly] is a polynomial with coefficients in a [ModuloRing].
 //!
 //! Trying to mix different rings (even with the same modulus!) will cause a panic.
 //!
@@ -15,16 +18,27 @@
 //! assert_eq!(format!("{}", x - y), "6902 (mod 10000)");
 //! ```
 
-pub use convert::IntoModulo;
+pub use const_modulo::ConstModulo;
+pub use convert::{Centered, IntoModulo};
+pub use fixed_base::FixedBasePow;
 pub use modulo::Modulo;
 pub use modulo_ring::ModuloRing;
+pub use owned::ModuloOwned;
+pub use poly::ModPoly;
 
 mod add;
+mod barrett;
 mod cmp;
+mod const_modulo;
 pub(crate) mod convert;
+mod crt;
 mod div;
+mod fixed_base;
 mod fmt;
 pub(crate) mod modulo;
 pub(crate) mod modulo_ring;
+mod montgomery;
 mod mul;
+mod owned;
+mod poly;
 mod pow;
@@ -3,7 +3,7 @@
 use crate::{
     arch::word::Word,
     math,
-    modular::modulo_ring::{ModuloRingLarge, ModuloRingSmall},
+    modular::modulo_ring::{ModuloRing, ModuloRingLarge, ModuloRingRepr, ModuloRingSmall},
 };
 use alloc::vec::Vec;
 
@@ -26,7 +26,7 @@ pub(crate) enum ModuloRepr<'a> {
 }
 
 /// Modular value in some unknown ring. The ring must be provided to operations.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub(crate) struct ModuloSmallRaw {
     /// must be in range 0..modulus and divisible by the shift for ModuloSmallRing
     normalized_value: Word,
@@ -57,10 +57,41 @@ impl<'a> Modulo<'a> {
         &mut self.0
     }
 
+    /// Take representation.
+    #[inline]
+    pub(crate) fn into_repr(self) -> ModuloRepr<'a> {
+        self.0
+    }
+
+    /// Whether the value is zero.
+    #[inline]
+    pub(crate) fn is_zero(&self) -> bool {
+        match self.repr() {
+            ModuloRepr::Small(self_small) => self_small.raw().normalized() == 0,
+            ModuloRepr::Large(self_large) => self_large.normalized_value().iter().all(|&w| w == 0),
+        }
+    }
+
     /// Panics when trying to do operations on [Modulo] values from different rings.
     pub(crate) fn panic_different_rings() -> ! {
         panic!("Modulo values from different rings")
     }
+
+    /// Checks that the value is from `ring`.
+    pub(crate) fn check_ring(&self, ring: &ModuloRing) {
+        let same_ring = match (self.repr(), ring.repr()) {
+            (ModuloRepr::Small(self_small), ModuloRingRepr::Small(ring_small)) => {
+                self_small.ring() == ring_small
+            }
+            (ModuloRepr::Large(self_large), ModuloRingRepr::Large(ring_large)) => {
+                self_large.ring() == ring_large
+            }
+            _ => false,
+        };
+        if !same_ring {
+            Modulo::panic_different_rings();
+        }
+    }
 }
 
 impl<'a> From<ModuloSmall<'a>> for Modulo<'a> {
@@ -150,6 +181,11 @@ impl<'a> ModuloLarge<'a> {
         &self.normalized_value
     }
 
+    /// Take normalized value.
+    pub(crate) fn into_normalized_value(self) -> Vec<Word> {
+        self.normalized_value
+    }
+
     /// Modify normalized value.
     pub(crate) fn modify_normalized_value<F>(&mut self, f: F)
     where
@@ -6,6 +6,7 @@ use crate::{
     cmp, div,
     fast_divide::FastDivideNormalized,
     math,
+    modular::{crt::Crt, montgomery::Montgomery},
     ubig::{Repr, UBig},
 };
 use alloc::vec::Vec;
@@ -37,6 +38,10 @@ pub(crate) struct ModuloRingLarge {
     normalized_modulus: Vec<Word>,
     shift: u32,
     fast_div_top: FastDivideNormalized,
+    /// Odd moduli store values in Montgomery representation.
+    montgomery: Option<Montgomery>,
+    /// Known factorization of the modulus, for exponentiation.
+    crt: Option<Crt>,
 }
 
 impl ModuloRing {
@@ -67,6 +72,42 @@ impl ModuloRing {
         }
     }
 
+    /// Create a new ring of integers modulo `n` from the factorization of `n`.
+    ///
+    /// `factors` are pairs `(p, k)` of distinct primes `p` and exponents `k`, where `n` is the
+    /// product of all `p^k`, as returned by [UBig::factor]. [Modulo::pow](crate::modular::Modulo::pow)
+    /// in this ring then works modulo each `p^k` with the exponent reduced by `phi(p^k)`, and
+    /// combines the results with the Chinese remainder theorem. For an RSA modulus `n = p q`
+    /// this makes private-key exponentiation about 4 times faster.
+    ///
+    /// If `n` fits in a machine word, the factorization is not used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::ModuloRing, ubig};
+    /// let p = ubig!(2).pow(127) - ubig!(1);
+    /// let q = ubig!(2).pow(89) - ubig!(1);
+    /// let ring = ModuloRing::with_factorization(&[(p.clone(), 1), (q.clone(), 2)]);
+    /// assert_eq!(ring.modulus(), &p * &q * &q);
+    ///
+    /// let plain_ring = ModuloRing::new(&ring.modulus());
+    /// let e = ubig!(2).pow(1000) + ubig!(12345);
+    /// assert_eq!(ring.from(3).pow(&e).residue(), plain_ring.from(3).pow(&e).residue());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if some `p` is not prime, some `k` is zero, or some `p` appears twice.
+    pub fn with_factorization(factors: &[(UBig, usize)]) -> ModuloRing {
+        let (n, crt) = Crt::new(factors);
+        let mut ring = ModuloRing::new(&n);
+        if let ModuloRingRepr::Large(ring_large) = &mut ring.0 {
+            ring_large.crt = Some(crt);
+        }
+        ring
+    }
+
     #[inline]
     pub(crate) fn repr(&self) -> &ModuloRingRepr {
         &self.0
@@ -109,10 +150,17 @@ impl ModuloRingLarge {
     fn new(n: &[Word]) -> ModuloRingLarge {
         let mut normalized_modulus = n.to_vec();
         let (shift, fast_div_top) = div::normalize_large(&mut normalized_modulus);
+        let montgomery = if n[0] & 1 == 1 {
+            Some(Montgomery::new(n, &normalized_modulus, shift))
+        } else {
+            None
+        };
         ModuloRingLarge {
             normalized_modulus,
             shift,
             fast_div_top,
+            montgomery,
+            crt: None,
         }
     }
 
@@ -128,6 +176,14 @@ impl ModuloRingLarge {
         self.fast_div_top
     }
 
+    pub(crate) fn montgomery(&self) -> Option<&Montgomery> {
+        self.montgomery.as_ref()
+    }
+
+    pub(crate) fn crt(&self) -> Option<&Crt> {
+        self.crt.as_ref()
+    }
+
     pub(crate) fn is_valid(&self, val: &[Word]) -> bool {
         val.len() == self.normalized_modulus.len()
             && cmp::cmp_same_len(val, &self.normalized_modulus) == Ordering::Less
@@ -0,0 +1,265 @@
+//! Montgomery representation for odd moduli.
+//!
+//! For an odd modulus `m` of `n` words let `R = 2^(WORD_BITS * n)`. A value `x` is stored as
+//! `x * R mod m`. The product of two such values is reduced by REDC, which computes `T / R mod m`
+//! without any division.
+
+use crate::{
+    add,
+    arch::{self, word::Word},
+    cmp,
+    memory::{self, Memory},
+    mul,
+    primitive::{extend_word, split_double_word, WORD_BITS, WORD_BITS_USIZE},
+    sign::Sign::Positive,
+    ubig::UBig,
+};
+use alloc::{alloc::Layout, vec::Vec};
+use core::cmp::Ordering;
+
+/// If the modulus is at least this long, REDC uses multiplication instead of reducing one word
+/// at a time.
+const MIN_LEN_REDC_MUL: usize = arch::thresholds::REDC_MIN_LEN_MUL;
+
+/// Parameters of the Montgomery representation.
+pub(crate) struct Montgomery {
+    /// The modulus `m`, not normalized.
+    modulus: Vec<Word>,
+    /// `-m^-1 mod 2^WORD_BITS`
+    neg_inv_word: Word,
+    /// `-m^-1 mod R`, if REDC uses multiplication.
+    neg_inv: Option<Vec<Word>>,
+    /// `R^2 mod m`, normalized.
+    normalized_r2: Vec<Word>,
+}
+
+impl Montgomery {
+    /// Montgomery parameters for an odd modulus with normalized form `normalized_modulus`.
+    pub(crate) fn new(modulus: &[Word], normalized_modulus: &[Word], shift: u32) -> Montgomery {
+        let n = modulus.len();
+        debug_assert!(modulus[0] & 1 == 1 && normalized_modulus.len() == n);
+
+        let neg_inv_word = neg_inv_word(modulus[0]);
+        let neg_inv = if n >= MIN_LEN_REDC_MUL {
+            Some(neg_inv_large(modulus, neg_inv_word))
+        } else {
+            None
+        };
+
+        // (2^(2 * WORD_BITS * n) mod m) << shift == 2^(2 * WORD_BITS * n + shift) mod normalized_m
+        let r2 = (UBig::from_word(1) << (2 * n * WORD_BITS_USIZE + shift as usize))
+            % UBig::from_words(normalized_modulus);
+        let mut normalized_r2 = r2.as_words().to_vec();
+        normalized_r2.resize(n, 0);
+
+        Montgomery {
+            modulus: modulus.to_vec(),
+            neg_inv_word,
+            neg_inv,
+            normalized_r2,
+        }
+    }
+
+    /// `R^2 mod m`, normalized.
+    ///
+    /// Montgomery multiplication by this converts into the Montgomery representation.
+    pub(crate) fn normalized_r2(&self) -> &[Word] {
+        &self.normalized_r2
+    }
+
+    /// Temporary scratch space required for `redc`.
+    pub(crate) fn redc_memory_requirement(&self) -> Layout {
+        let n = self.modulus.len();
+        if self.neg_inv.is_some() {
+            memory::add_layout(
+                memory::array_layout::<Word>(2 * n),
+                mul::memory_requirement_exact(2 * n, n),
+            )
+        } else {
+            memory::zero_layout()
+        }
+    }
+
+    /// REDC: replace `t` by `t / R mod m`.
+    ///
+    /// `t` has `2n` words and `t < m * R`. The result is stored in `t[n..]`.
+    pub(crate) fn redc(&self, t: &mut [Word], memory: &mut Memory) {
+        let n = self.modulus.len();
+        debug_assert!(t.len() == 2 * n);
+
+        // t := t + q * m with t divisible by R, q < R.
+        // t < m * R + R * m, so at most 1 bit of overflow.
+        let overflow = match &self.neg_inv {
+            None => self.add_redc_multiple_by_word(t),
+            Some(neg_inv) => self.add_redc_multiple_by_mul(t, neg_inv, memory),
+        };
+
+        // t / R < 2m, subtract m at most once.
+        let res = &mut t[n..];
+        if overflow || cmp::cmp_same_len(res, &self.modulus) != Ordering::Less {
+            let borrow = add::sub_same_len_in_place(res, &self.modulus);
+            debug_assert!(borrow == overflow);
+        }
+    }
+
+    /// t += q * m, where q is chosen one word at a time to zero the low words of t.
+    ///
+    /// Returns overflow.
+    fn add_redc_multiple_by_word(&self, t: &mut [Word]) -> bool {
+        let n = self.modulus.len();
+        let mut overflow = false;
+        for i in 0..n {
+            let q = t[i].wrapping_mul(self.neg_inv_word);
+            let carry = mul::add_mul_word_same_len_in_place(&mut t[i..i + n], q, &self.modulus);
+            debug_assert!(t[i] == 0);
+            // The total only overflows once.
+            overflow |= add::add_word_in_place(&mut t[i + n..], carry);
+        }
+        overflow
+    }
+
+    /// t += q * m, where q = t * (-m^-1) mod R.
+    ///
+    /// Returns overflow.
+    fn add_redc_multiple_by_mul(
+        &self,
+        t: &mut [Word],
+        neg_inv: &[Word],
+        memory: &mut Memory,
+    ) -> bool {
+        let n = self.modulus.len();
+        let (product, mut memory) = memory.allocate_slice_fill::<Word>(2 * n, 0);
+        let overflow =
+            mul::add_signed_mul_same_len(product, Positive, &t[..n], neg_inv, &mut memory);
+        debug_assert!(overflow == 0);
+        let q = &product[..n];
+        let overflow = mul::add_signed_mul_same_len(t, Positive, q, &self.modulus, &mut memory);
+        debug_assert!(t[..n].iter().all(|&w| w == 0));
+        debug_assert!(overflow == 0 || overflow == 1);
+        overflow != 0
+    }
+
+    /// Temporary words required for `mul_ct`.
+    pub(crate) fn mul_ct_len(&self) -> usize {
+        self.modulus.len() + 2
+    }
+
+    /// Montgomery product `a * b / R mod m` in constant time.
+    ///
+    /// `a` and `b` are not normalized and less than `m`. The result is stored in `t[..n]`, where
+    /// `t` has `n + 2` words.
+    ///
+    /// Interleaves multiplication and reduction one word at a time (CIOS). The running time only
+    /// depends on `n`: there are no branches or early exits depending on the values, unlike the
+    /// general multiplication and `redc`.
+    pub(crate) fn mul_ct(&self, a: &[Word], b: &[Word], t: &mut [Word]) {
+        let n = self.modulus.len();
+        debug_assert!(a.len() == n && b.len() == n && t.len() == n + 2);
+        t.fill(0);
+        for &a_word in a {
+            // t += a_word * b
+            let carry = add_mul_word_ct(&mut t[..n], a_word, b);
+            let (sum, overflow) = arch::add::add_with_carry(t[n], carry, false);
+            t[n] = sum;
+            t[n + 1] = Word::from(overflow);
+
+            // t += q * m, t[0] becomes 0
+            let q = t[0].wrapping_mul(self.neg_inv_word);
+            let carry = add_mul_word_ct(&mut t[..n], q, &self.modulus);
+            let (sum, overflow) = arch::add::add_with_carry(t[n], carry, false);
+            t[n] = sum;
+            t[n + 1] += Word::from(overflow);
+
+            // t /= 2^WORD_BITS
+            t.copy_within(1.., 0);
+            t[n + 1] = 0;
+        }
+
+        // t < 2m
+        sub_if_not_less_ct(&mut t[..n + 1], &self.modulus);
+    }
+}
+
+/// words += mult * rhs, in constant time.
+///
+/// Returns carry.
+///
+/// Unlike `mul::add_mul_word_same_len_in_place`, no architecture kernel with a data-dependent
+/// tail is involved.
+pub(crate) fn add_mul_word_ct(words: &mut [Word], mult: Word, rhs: &[Word]) -> Word {
+    debug_assert!(words.len() == rhs.len());
+    let mut carry: Word = 0;
+    for (a, b) in words.iter_mut().zip(rhs.iter()) {
+        // a + mult * b + carry <= MAX * MAX + 2 * MAX <= DoubleWord::MAX
+        let (v_lo, v_hi) = split_double_word(
+            extend_word(*a) + extend_word(carry) + extend_word(mult) * extend_word(*b),
+        );
+        *a = v_lo;
+        carry = v_hi;
+    }
+    carry
+}
+
+/// `x -= m` if `x >= m`, in constant time. `m` is zero-extended to the length of `x`.
+///
+/// Subtracts unless that borrows, selecting with a mask rather than a branch.
+pub(crate) fn sub_if_not_less_ct(x: &mut [Word], m: &[Word]) {
+    debug_assert!(m.len() <= x.len());
+    let mut borrow = false;
+    for (i, &x_word) in x.iter().enumerate() {
+        let m_word = m.get(i).copied().unwrap_or(0);
+        borrow = arch::add::sub_with_borrow(x_word, m_word, borrow).1;
+    }
+    let mask = Word::from(borrow).wrapping_sub(1);
+    // Without the volatile read, the optimizer sees that the mask is either zero or all
+    // ones and splits the loop below with a branch on `borrow`.
+    // SAFETY: reading a local variable.
+    let mask = unsafe { core::ptr::read_volatile(&mask) };
+    let mut borrow = false;
+    for (i, x_word) in x.iter_mut().enumerate() {
+        let m_word = m.get(i).copied().unwrap_or(0);
+        let (diff, b) = arch::add::sub_with_borrow(*x_word, m_word & mask, borrow);
+        *x_word = diff;
+        borrow = b;
+    }
+}
+
+/// `-m^-1 mod 2^WORD_BITS` for odd `m`.
+fn neg_inv_word(m: Word) -> Word {
+    debug_assert!(m & 1 == 1);
+    // m * m == 1 mod 8, so m is its own inverse to 3 bits.
+    // Each Newton step doubles the number of correct bits.
+    let mut inv = m;
+    let mut bits = 3;
+    while bits < WORD_BITS {
+        inv = inv.wrapping_mul((2 as Word).wrapping_sub(m.wrapping_mul(inv)));
+        bits *= 2;
+    }
+    debug_assert!(m.wrapping_mul(inv) == 1);
+    inv.wrapping_neg()
+}
+
+/// `-m^-1 mod R` for odd `m`, where `R = 2^(WORD_BITS * m.len())`.
+fn neg_inv_large(m: &[Word], neg_inv_word: Word) -> Vec<Word> {
+    let n = m.len();
+    let total_bits = n * WORD_BITS_USIZE;
+    let m = UBig::from_words(m);
+
+    // Newton's method: inv := inv * (2 - m * inv) mod 2^bits doubles the number of correct bits.
+    let mut inv = UBig::from_word(neg_inv_word.wrapping_neg());
+    let mut bits = WORD_BITS_USIZE;
+    while bits < total_bits {
+        bits = (2 * bits).min(total_bits);
+        let pow = UBig::from_word(1) << bits;
+        let mask = &pow - UBig::from_word(1);
+        // m * inv == 1 mod 2^(bits/2), so 2 - m * inv == 2^bits + 2 - (m * inv mod 2^bits)
+        // modulo 2^bits.
+        let m_inv = (&m * &inv) & &mask;
+        inv = (inv * (pow + UBig::from_word(2) - m_inv)) & mask;
+    }
+
+    let neg_inv = (UBig::from_word(1) << total_bits) - inv;
+    let mut words = neg_inv.as_words().to_vec();
+    words.resize(n, 0);
+    words
+}
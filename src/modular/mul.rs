@@ -79,6 +79,25 @@ impl<'a> MulAssign<&Modulo<'a>> for Modulo<'a> {
     }
 }
 
+impl Modulo<'_> {
+    /// self *= self
+    #[inline]
+    pub(crate) fn square_in_place(&mut self) {
+        match self.repr_mut() {
+            ModuloRepr::Small(self_small) => {
+                let raw = self_small.raw();
+                self_small.set_raw(raw.mul(raw, self_small.ring()));
+            }
+            ModuloRepr::Large(self_large) => {
+                let memory_requirement = self_large.ring().mul_memory_requirement();
+                let mut allocation = MemoryAllocation::new(memory_requirement);
+                let mut memory = allocation.memory();
+                self_large.square_in_place(&mut memory);
+            }
+        }
+    }
+}
+
 impl ModuloSmallRaw {
     #[inline]
     pub(crate) const fn mul(self, other: ModuloSmallRaw, ring: &ModuloRingSmall) -> ModuloSmallRaw {
@@ -103,16 +122,22 @@ impl<'a> ModuloSmall<'a> {
 impl ModuloRingLarge {
     pub(crate) fn mul_memory_requirement(&self) -> Layout {
         let n = self.normalized_modulus().len();
+        let reduce_memory_requirement = match self.montgomery() {
+            None => div::memory_requirement_exact(2 * n, n),
+            Some(montgomery) => montgomery.redc_memory_requirement(),
+        };
         memory::add_layout(
             memory::array_layout::<Word>(2 * n),
             memory::max_layout(
                 mul::memory_requirement_exact(2 * n, n),
-                div::memory_requirement_exact(2 * n, n),
+                reduce_memory_requirement,
             ),
         )
     }
 
     /// Returns a * b allocated in memory.
+    ///
+    /// In Montgomery representation this is the Montgomery product a * b / R.
     pub(crate) fn mul_normalized<'a>(
         &self,
         a: &[Word],
@@ -128,8 +153,21 @@ impl ModuloRingLarge {
         assert_eq!(overflow, 0);
         shift::shr_in_place(product, self.shift());
 
-        let _overflow = div::div_rem_in_place(product, modulus, self.fast_div_top(), &mut memory);
-        &product[..n]
+        match self.montgomery() {
+            None => {
+                let _overflow =
+                    div::div_rem_in_place(product, modulus, self.fast_div_top(), &mut memory);
+                &product[..n]
+            }
+            Some(montgomery) => {
+                // Both factors were normalized.
+                shift::shr_in_place(product, self.shift());
+                montgomery.redc(product, &mut memory);
+                let carry = shift::shl_in_place(&mut product[n..], self.shift());
+                debug_assert!(carry == 0);
+                &product[n..]
+            }
+        }
     }
 }
 
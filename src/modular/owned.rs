@@ -0,0 +1,364 @@
+//! Modular values that own a handle to their ring.
+
+use crate::{
+    arch::word::Word,
+    ibig::IBig,
+    modular::{
+        convert::IntoModulo,
+        modulo::{Modulo, ModuloLarge, ModuloRepr, ModuloSmall, ModuloSmallRaw},
+        modulo_ring::{ModuloRing, ModuloRingRepr},
+    },
+    ubig::UBig,
+};
+use alloc::vec::Vec;
+use core::{
+    mem,
+    ops::{Add, AddAssign, Deref, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// Modular arithmetic with the ring held by an owning handle.
+///
+/// [Modulo] borrows its [ModuloRing]. `ModuloOwned<R>` instead holds a handle `R` to the ring,
+/// such as `Rc<ModuloRing>`, `Arc<ModuloRing>` or `&'static ModuloRing`, so it can be stored in
+/// long-lived structs, returned from the function that created the ring, or, with `Arc`, sent
+/// across threads.
+///
+/// Values are compatible if their handles point to the same [ModuloRing].
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{modular::{ModuloOwned, ModuloRing}, ubig};
+/// use std::rc::Rc;
+///
+/// fn make() -> ModuloOwned<Rc<ModuloRing>> {
+///     let ring = Rc::new(ModuloRing::new(&ubig!(10000)));
+///     ModuloOwned::new(ring, 12345)
+/// }
+///
+/// let x = make();
+/// let y = ModuloOwned::new(x.ring().clone(), 55443);
+/// assert_eq!((x - y).residue(), ubig!(6902));
+/// ```
+#[derive(Clone)]
+pub struct ModuloOwned<R> {
+    ring: R,
+    value: ModuloOwnedRepr,
+}
+
+/// Value without the ring.
+#[derive(Clone, Eq, PartialEq)]
+enum ModuloOwnedRepr {
+    Small(ModuloSmallRaw),
+    /// Same as `ModuloLarge::normalized_value`.
+    Large(Vec<Word>),
+}
+
+impl ModuloOwnedRepr {
+    /// Detach a value from its ring.
+    fn detach(x: Modulo) -> ModuloOwnedRepr {
+        match x.into_repr() {
+            ModuloRepr::Small(x_small) => ModuloOwnedRepr::Small(x_small.raw()),
+            ModuloRepr::Large(x_large) => ModuloOwnedRepr::Large(x_large.into_normalized_value()),
+        }
+    }
+
+    /// Attach a value to the ring it was detached from.
+    fn attach(self, ring: &ModuloRing) -> Modulo<'_> {
+        match (self, ring.repr()) {
+            (ModuloOwnedRepr::Small(raw), ModuloRingRepr::Small(ring_small)) => {
+                ModuloSmall::new(raw, ring_small).into()
+            }
+            (ModuloOwnedRepr::Large(words), ModuloRingRepr::Large(ring_large)) => {
+                ModuloLarge::new(words, ring_large).into()
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<R: Deref<Target = ModuloRing>> ModuloOwned<R> {
+    /// Create a value in the ring `ring`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModuloOwned, ModuloRing}, ubig};
+    /// # use std::sync::Arc;
+    /// let ring = Arc::new(ModuloRing::new(&ubig!(100)));
+    /// let x = ModuloOwned::new(ring.clone(), 305);
+    /// assert_eq!(x.residue(), ubig!(5));
+    /// ```
+    #[inline]
+    pub fn new<T: IntoModulo>(ring: R, x: T) -> ModuloOwned<R> {
+        let value = ModuloOwnedRepr::detach(ring.from(x));
+        ModuloOwned { ring, value }
+    }
+
+    /// Convert a [Modulo] value from the ring `ring`.
+    ///
+    /// The value is moved, not copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModuloOwned, ModuloRing}, ubig};
+    /// # use std::rc::Rc;
+    /// let ring = Rc::new(ModuloRing::new(&ubig!(100)));
+    /// let x = ring.from(7) * ring.from(8);
+    /// let y = ModuloOwned::from_modulo(ring.clone(), x);
+    /// assert_eq!(y.residue(), ubig!(56));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is not from the ring `ring`.
+    pub fn from_modulo(ring: R, x: Modulo) -> ModuloOwned<R> {
+        x.check_ring(&ring);
+        let value = ModuloOwnedRepr::detach(x);
+        ModuloOwned { ring, value }
+    }
+
+    /// The ring handle.
+    #[inline]
+    pub fn ring(&self) -> &R {
+        &self.ring
+    }
+
+    /// Convert to a [Modulo] value borrowing the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModuloOwned, ModuloRing}, ubig};
+    /// # use std::rc::Rc;
+    /// let ring = Rc::new(ModuloRing::new(&ubig!(100)));
+    /// let x = ModuloOwned::new(ring.clone(), 7);
+    /// assert_eq!(x.to_modulo() * ring.from(8), ring.from(56));
+    /// ```
+    #[inline]
+    pub fn to_modulo(&self) -> Modulo<'_> {
+        self.value.clone().attach(&self.ring)
+    }
+
+    /// Get the residue in range `0..n` in an n-element ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModuloOwned, ModuloRing}, ubig};
+    /// # use std::rc::Rc;
+    /// let ring = Rc::new(ModuloRing::new(&ubig!(100)));
+    /// let x = ModuloOwned::new(ring, -1234);
+    /// assert_eq!(x.residue(), ubig!(66));
+    /// ```
+    #[inline]
+    pub fn residue(&self) -> UBig {
+        self.to_modulo().residue()
+    }
+
+    /// Get the centered residue in range `(-n/2, n/2]` in an n-element ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, modular::{ModuloOwned, ModuloRing}, ubig};
+    /// # use std::rc::Rc;
+    /// let ring = Rc::new(ModuloRing::new(&ubig!(100)));
+    /// let x = ModuloOwned::new(ring, -1234);
+    /// assert_eq!(x.centered_residue(), ibig!(-34));
+    /// ```
+    #[inline]
+    pub fn centered_residue(&self) -> IBig {
+        self.to_modulo().centered_residue()
+    }
+
+    /// Take the value out, leaving a placeholder.
+    ///
+    /// The caller attaches it to `self.ring` and puts the result back.
+    #[inline]
+    fn take_value(&mut self) -> ModuloOwnedRepr {
+        mem::replace(&mut self.value, ModuloOwnedRepr::Large(Vec::new()))
+    }
+
+    /// Checks that two values are from the same ring.
+    #[inline]
+    fn check_same_ring(&self, other: &ModuloOwned<R>) {
+        if *self.ring != *other.ring {
+            Modulo::panic_different_rings();
+        }
+    }
+}
+
+impl<R: Deref<Target = ModuloRing> + Clone> ModuloOwned<R> {
+    /// Inverse.
+    ///
+    /// Returns `None` if there is no unique inverse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModuloOwned, ModuloRing}, ubig};
+    /// # use std::rc::Rc;
+    /// let ring = Rc::new(ModuloRing::new(&ubig!(10)));
+    /// let x = ModuloOwned::new(ring.clone(), 7);
+    /// assert_eq!(x.inverse(), Some(ModuloOwned::new(ring.clone(), 3)));
+    /// assert_eq!(ModuloOwned::new(ring, 2).inverse(), None);
+    /// ```
+    #[inline]
+    pub fn inverse(&self) -> Option<ModuloOwned<R>> {
+        let inv = ModuloOwnedRepr::detach(self.to_modulo().inverse()?);
+        Some(ModuloOwned {
+            ring: self.ring.clone(),
+            value: inv,
+        })
+    }
+
+    /// Exponentiation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModuloOwned, ModuloRing}, ubig};
+    /// # use std::rc::Rc;
+    /// let ring = Rc::new(ModuloRing::new(&ubig!(100)));
+    /// let x = ModuloOwned::new(ring, 13);
+    /// assert_eq!(x.pow(&ubig!(2)).residue(), ubig!(69));
+    /// ```
+    #[inline]
+    pub fn pow(&self, exp: &UBig) -> ModuloOwned<R> {
+        ModuloOwned {
+            ring: self.ring.clone(),
+            value: ModuloOwnedRepr::detach(self.to_modulo().pow(exp)),
+        }
+    }
+
+    /// Exponentiation to a signed exponent.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the exponent is negative and the base is not invertible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{ibig, modular::{ModuloOwned, ModuloRing}, ubig};
+    /// # use std::rc::Rc;
+    /// let ring = Rc::new(ModuloRing::new(&ubig!(10)));
+    /// let x = ModuloOwned::new(ring, 3);
+    /// assert_eq!(x.pow_signed(&ibig!(-3)).residue(), ubig!(3));
+    /// ```
+    #[inline]
+    pub fn pow_signed(&self, exp: &IBig) -> ModuloOwned<R> {
+        ModuloOwned {
+            ring: self.ring.clone(),
+            value: ModuloOwnedRepr::detach(self.to_modulo().pow_signed(exp)),
+        }
+    }
+}
+
+/// Equality within a ring.
+///
+/// # Panics
+///
+/// Panics if the two values are from different rings.
+impl<R: Deref<Target = ModuloRing>> PartialEq for ModuloOwned<R> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.check_same_ring(other);
+        self.value == other.value
+    }
+}
+
+impl<R: Deref<Target = ModuloRing>> Eq for ModuloOwned<R> {}
+
+impl<R: Deref<Target = ModuloRing>> Neg for ModuloOwned<R> {
+    type Output = ModuloOwned<R>;
+
+    #[inline]
+    fn neg(mut self) -> ModuloOwned<R> {
+        let x = self.take_value().attach(&self.ring);
+        self.value = ModuloOwnedRepr::detach(-x);
+        self
+    }
+}
+
+impl<R: Deref<Target = ModuloRing> + Clone> Neg for &ModuloOwned<R> {
+    type Output = ModuloOwned<R>;
+
+    #[inline]
+    fn neg(self) -> ModuloOwned<R> {
+        self.clone().neg()
+    }
+}
+
+macro_rules! impl_binop {
+    ($tr:ident, $f:ident, $tr_assign:ident, $f_assign:ident) => {
+        impl<R: Deref<Target = ModuloRing>> $tr<ModuloOwned<R>> for ModuloOwned<R> {
+            type Output = ModuloOwned<R>;
+
+            #[inline]
+            fn $f(mut self, rhs: ModuloOwned<R>) -> ModuloOwned<R> {
+                self.$f_assign(rhs);
+                self
+            }
+        }
+
+        impl<R: Deref<Target = ModuloRing>> $tr<&ModuloOwned<R>> for ModuloOwned<R> {
+            type Output = ModuloOwned<R>;
+
+            #[inline]
+            fn $f(mut self, rhs: &ModuloOwned<R>) -> ModuloOwned<R> {
+                self.$f_assign(rhs);
+                self
+            }
+        }
+
+        impl<R: Deref<Target = ModuloRing> + Clone> $tr<ModuloOwned<R>> for &ModuloOwned<R> {
+            type Output = ModuloOwned<R>;
+
+            #[inline]
+            fn $f(self, rhs: ModuloOwned<R>) -> ModuloOwned<R> {
+                self.clone().$f(rhs)
+            }
+        }
+
+        impl<R: Deref<Target = ModuloRing> + Clone> $tr<&ModuloOwned<R>> for &ModuloOwned<R> {
+            type Output = ModuloOwned<R>;
+
+            #[inline]
+            fn $f(self, rhs: &ModuloOwned<R>) -> ModuloOwned<R> {
+                self.clone().$f(rhs)
+            }
+        }
+
+        impl<R: Deref<Target = ModuloRing>> $tr_assign<ModuloOwned<R>> for ModuloOwned<R> {
+            #[inline]
+            fn $f_assign(&mut self, rhs: ModuloOwned<R>) {
+                self.check_same_ring(&rhs);
+                let ModuloOwned {
+                    ring: rhs_ring,
+                    value: rhs_value,
+                } = rhs;
+                let mut x = self.take_value().attach(&self.ring);
+                x.$f_assign(rhs_value.attach(&rhs_ring));
+                self.value = ModuloOwnedRepr::detach(x);
+            }
+        }
+
+        impl<R: Deref<Target = ModuloRing>> $tr_assign<&ModuloOwned<R>> for ModuloOwned<R> {
+            #[inline]
+            fn $f_assign(&mut self, rhs: &ModuloOwned<R>) {
+                self.check_same_ring(rhs);
+                let mut x = self.take_value().attach(&self.ring);
+                x.$f_assign(rhs.to_modulo());
+                self.value = ModuloOwnedRepr::detach(x);
+            }
+        }
+    };
+}
+
+impl_binop!(Add, add, AddAssign, add_assign);
+impl_binop!(Sub, sub, SubAssign, sub_assign);
+impl_binop!(Mul, mul, MulAssign, mul_assign);
+impl_binop!(Div, div, DivAssign, div_assign);
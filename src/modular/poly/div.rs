@@ -0,0 +1,254 @@
+//! Polynomial division.
+
+use super::{
+    mul::{mul, NttRoots},
+    sub_in_place, ModPoly,
+};
+use crate::{
+    modular::{modulo::Modulo, modulo_ring::ModuloRing},
+    ops::DivRem,
+};
+use alloc::{vec, vec::Vec};
+use core::{
+    mem,
+    ops::{Div, DivAssign, Rem, RemAssign},
+};
+
+/// If both the divisor and the quotient have at least this many coefficients, divide using
+/// Newton's iteration for the inverse of the divisor.
+const MIN_LEN_DIV_NEWTON: usize = 384;
+
+impl<'a> ModPoly<'a> {
+    /// Greatest common divisor, made monic.
+    ///
+    /// The gcd of two zero polynomials is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModPoly, ModuloRing}, ubig};
+    /// let ring = ModuloRing::new(&ubig!(7));
+    /// // (x + 1)(x + 2) and 3(x + 1)(x + 3)
+    /// let a = ModPoly::new(&ring, [2, 3, 1]);
+    /// let b = ModPoly::new(&ring, [9, 12, 3]);
+    /// assert_eq!(a.gcd(&b), ModPoly::new(&ring, [1, 1]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if some remainder in the Euclidean algorithm has a non-invertible leading
+    /// coefficient. This can't happen if the modulus is prime.
+    pub fn gcd(&self, rhs: &ModPoly<'a>) -> ModPoly<'a> {
+        self.check_same_ring(rhs);
+        let ntt_roots = div_ntt_roots(self.ring, self.coeffs.len().max(rhs.coeffs.len()));
+        let mut a = self.clone();
+        let mut b = rhs.clone();
+        while !b.is_zero() {
+            let r = a.div_rem_with(&ntt_roots, &b).1;
+            a = mem::replace(&mut b, r);
+        }
+        match a.leading_coeff() {
+            None => a,
+            Some(lead) => match lead.inverse() {
+                None => panic_non_invertible_leading_coeff(),
+                Some(inv) => {
+                    for c in &mut a.coeffs {
+                        *c *= &inv;
+                    }
+                    a
+                }
+            },
+        }
+    }
+
+    /// (self / rhs, self % rhs)
+    fn div_rem_ref(&self, rhs: &ModPoly<'a>) -> (ModPoly<'a>, ModPoly<'a>) {
+        self.div_rem_with(&div_ntt_roots(self.ring, self.coeffs.len()), rhs)
+    }
+
+    /// (self / rhs, self % rhs), multiplying with `ntt_roots`.
+    ///
+    /// `ntt_roots` should cover products of up to twice the length of `self`.
+    pub(super) fn div_rem_with(
+        &self,
+        ntt_roots: &NttRoots<'a>,
+        rhs: &ModPoly<'a>,
+    ) -> (ModPoly<'a>, ModPoly<'a>) {
+        self.check_same_ring(rhs);
+        let lead_inv = match rhs.leading_coeff() {
+            None => panic!("Division by a zero ModPoly"),
+            Some(lead) => match lead.inverse() {
+                None => panic_non_invertible_leading_coeff(),
+                Some(inv) => inv,
+            },
+        };
+        if self.coeffs.len() < rhs.coeffs.len() {
+            return (ModPoly::zero(self.ring), self.clone());
+        }
+
+        let quotient_len = self.coeffs.len() - rhs.coeffs.len() + 1;
+        let (q, r) = if quotient_len.min(rhs.coeffs.len()) >= MIN_LEN_DIV_NEWTON {
+            div_rem_newton(self.ring, ntt_roots, &self.coeffs, &rhs.coeffs, &lead_inv)
+        } else {
+            div_rem_classical(self.ring, &self.coeffs, &rhs.coeffs, &lead_inv)
+        };
+        (
+            ModPoly::from_vec(self.ring, q),
+            ModPoly::from_vec(self.ring, r),
+        )
+    }
+}
+
+fn panic_non_invertible_leading_coeff() -> ! {
+    panic!("Division by a ModPoly with a non-invertible leading coefficient")
+}
+
+/// Roots of unity for dividing polynomials of up to `len` coefficients.
+///
+/// Newton's division multiplies polynomials of up to twice that length. Shorter polynomials are
+/// divided without it, so no roots are searched for.
+fn div_ntt_roots<'a>(ring: &'a ModuloRing, len: usize) -> NttRoots<'a> {
+    if len >= 2 * MIN_LEN_DIV_NEWTON - 1 {
+        NttRoots::new(ring, 2 * len)
+    } else {
+        NttRoots::new(ring, 0)
+    }
+}
+
+/// Long division of `a` by `b`, where `b` has leading coefficient inverse `lead_inv`.
+///
+/// `a` is at least as long as `b`.
+fn div_rem_classical<'a>(
+    ring: &'a ModuloRing,
+    a: &[Modulo<'a>],
+    b: &[Modulo<'a>],
+    lead_inv: &Modulo<'a>,
+) -> (Vec<Modulo<'a>>, Vec<Modulo<'a>>) {
+    debug_assert!(a.len() >= b.len());
+    let mut r = a.to_vec();
+    let mut q = vec![ring.from(0); a.len() - b.len() + 1];
+    for i in (0..q.len()).rev() {
+        let c = &r[i + b.len() - 1] * lead_inv;
+        for (x, y) in r[i..].iter_mut().zip(b) {
+            *x -= &c * y;
+        }
+        q[i] = c;
+    }
+    r.truncate(b.len() - 1);
+    (q, r)
+}
+
+/// Division of `a` by `b` via a power series inverse of the reversed divisor.
+///
+/// With `rev(p)` the coefficients of `p` in reverse order, `rev(a / b) = rev(a) / rev(b)` modulo
+/// `x^(quotient length)`.
+fn div_rem_newton<'a>(
+    ring: &'a ModuloRing,
+    ntt_roots: &NttRoots<'a>,
+    a: &[Modulo<'a>],
+    b: &[Modulo<'a>],
+    lead_inv: &Modulo<'a>,
+) -> (Vec<Modulo<'a>>, Vec<Modulo<'a>>) {
+    debug_assert!(a.len() >= b.len());
+    let quotient_len = a.len() - b.len() + 1;
+    let b_rev: Vec<Modulo> = b.iter().rev().take(quotient_len).cloned().collect();
+    let a_rev: Vec<Modulo> = a.iter().rev().take(quotient_len).cloned().collect();
+    let b_rev_inv = inverse_series(ring, ntt_roots, &b_rev, lead_inv, quotient_len);
+    let mut q = mul(ring, ntt_roots, &a_rev, &b_rev_inv);
+    q.truncate(quotient_len);
+    q.resize(quotient_len, ring.from(0));
+    q.reverse();
+
+    // Only the low coefficients of q * b are needed, but they come from the full product.
+    let mut r = a[..b.len() - 1].to_vec();
+    let qb = mul(ring, ntt_roots, &q, b);
+    sub_in_place(&mut r, &qb[..b.len() - 1]);
+    (q, r)
+}
+
+/// `f^-1 mod x^len`, where `f[0]^-1` is `f0_inv`.
+///
+/// Newton's iteration doubles the number of correct coefficients: `g := g (2 - f g)`.
+fn inverse_series<'a>(
+    ring: &'a ModuloRing,
+    ntt_roots: &NttRoots<'a>,
+    f: &[Modulo<'a>],
+    f0_inv: &Modulo<'a>,
+    len: usize,
+) -> Vec<Modulo<'a>> {
+    let mut g = vec![f0_inv.clone()];
+    while g.len() < len {
+        let next_len = (2 * g.len()).min(len);
+        let mut e = mul(ring, ntt_roots, &f[..next_len.min(f.len())], &g);
+        e.truncate(next_len);
+        for x in &mut e {
+            *x = -&*x;
+        }
+        e[0] += ring.from(2);
+        g = mul(ring, ntt_roots, &g, &e);
+        g.truncate(next_len);
+    }
+    g
+}
+
+impl<'a> DivRem<&ModPoly<'a>> for &ModPoly<'a> {
+    type OutputDiv = ModPoly<'a>;
+    type OutputRem = ModPoly<'a>;
+
+    /// Division with remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero or its leading coefficient is not invertible.
+    #[inline]
+    fn div_rem(self, rhs: &ModPoly<'a>) -> (ModPoly<'a>, ModPoly<'a>) {
+        self.div_rem_ref(rhs)
+    }
+}
+
+impl<'a> DivRem<ModPoly<'a>> for &ModPoly<'a> {
+    type OutputDiv = ModPoly<'a>;
+    type OutputRem = ModPoly<'a>;
+
+    #[inline]
+    fn div_rem(self, rhs: ModPoly<'a>) -> (ModPoly<'a>, ModPoly<'a>) {
+        self.div_rem_ref(&rhs)
+    }
+}
+
+impl<'a> DivRem<&ModPoly<'a>> for ModPoly<'a> {
+    type OutputDiv = ModPoly<'a>;
+    type OutputRem = ModPoly<'a>;
+
+    #[inline]
+    fn div_rem(self, rhs: &ModPoly<'a>) -> (ModPoly<'a>, ModPoly<'a>) {
+        self.div_rem_ref(rhs)
+    }
+}
+
+impl<'a> DivRem<ModPoly<'a>> for ModPoly<'a> {
+    type OutputDiv = ModPoly<'a>;
+    type OutputRem = ModPoly<'a>;
+
+    #[inline]
+    fn div_rem(self, rhs: ModPoly<'a>) -> (ModPoly<'a>, ModPoly<'a>) {
+        self.div_rem_ref(&rhs)
+    }
+}
+
+impl<'a> DivAssign<&ModPoly<'a>> for ModPoly<'a> {
+    #[inline]
+    fn div_assign(&mut self, rhs: &ModPoly<'a>) {
+        *self = self.div_rem_ref(rhs).0;
+    }
+}
+
+impl<'a> RemAssign<&ModPoly<'a>> for ModPoly<'a> {
+    #[inline]
+    fn rem_assign(&mut self, rhs: &ModPoly<'a>) {
+        *self = self.div_rem_ref(rhs).1;
+    }
+}
+
+super::impl_binop!(Div, div, DivAssign, div_assign);
+super::impl_binop!(Rem, rem, RemAssign, rem_assign);
@@ -0,0 +1,211 @@
+//! Evaluation and interpolation.
+
+use super::{
+    mul::{mul, NttRoots},
+    ModPoly,
+};
+use crate::modular::{modulo::Modulo, modulo_ring::ModuloRing};
+use alloc::{vec, vec::Vec};
+
+/// If there are at least this many points and coefficients, evaluate using a subproduct tree.
+const MIN_LEN_EVAL_TREE: usize = 1024;
+
+impl<'a> ModPoly<'a> {
+    /// Evaluate at `x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModPoly, ModuloRing}, ubig};
+    /// let ring = ModuloRing::new(&ubig!(100));
+    /// let p = ModPoly::new(&ring, [1, 2, 3]);
+    /// assert_eq!(p.evaluate(&ring.from(10)), ring.from(21));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is not from the same ring.
+    pub fn evaluate(&self, x: &Modulo<'a>) -> Modulo<'a> {
+        x.check_ring(self.ring);
+        self.horner(x)
+    }
+
+    /// Evaluate at many points.
+    ///
+    /// For many points, the remainders modulo `x - point` are computed down a tree of products
+    /// of the `x - point` factors, which is faster than evaluating at each point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModPoly, ModuloRing}, ubig};
+    /// let ring = ModuloRing::new(&ubig!(100));
+    /// let p = ModPoly::new(&ring, [1, 2, 3]);
+    /// let points = [ring.from(0), ring.from(10)];
+    /// assert_eq!(p.evaluate_many(&points), [ring.from(1), ring.from(21)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if some point is not from the same ring.
+    pub fn evaluate_many(&self, points: &[Modulo<'a>]) -> Vec<Modulo<'a>> {
+        for x in points {
+            x.check_ring(self.ring);
+        }
+        if points.len().min(self.coeffs.len()) < MIN_LEN_EVAL_TREE {
+            points.iter().map(|x| self.horner(x)).collect()
+        } else {
+            // The remainders divide polynomials of up to this length.
+            let len = self.coeffs.len().max(points.len() + 1);
+            let ntt_roots = NttRoots::new(self.ring, 2 * len);
+            SubproductTree::new(self.ring, &ntt_roots, points).remainders(&ntt_roots, self)
+        }
+    }
+
+    /// The polynomial of degree less than `points.len()` with the given values at the points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModPoly, ModuloRing}, ubig};
+    /// let ring = ModuloRing::new(&ubig!(7));
+    /// let points = [ring.from(0), ring.from(1), ring.from(2)];
+    /// let values = [ring.from(1), ring.from(3), ring.from(0)];
+    /// let p = ModPoly::interpolate(&ring, &points, &values);
+    /// assert_eq!(p, ModPoly::new(&ring, [1, 1, 1]));
+    /// assert_eq!(p.evaluate_many(&points), values);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the numbers of points and values differ, if some point or value is not from
+    /// `ring`, or if the difference of some two points is not invertible. In particular, the
+    /// points must be distinct.
+    pub fn interpolate(
+        ring: &'a ModuloRing,
+        points: &[Modulo<'a>],
+        values: &[Modulo<'a>],
+    ) -> ModPoly<'a> {
+        assert_eq!(
+            points.len(),
+            values.len(),
+            "Different numbers of interpolation points and values"
+        );
+        for x in points.iter().chain(values) {
+            x.check_ring(ring);
+        }
+        // In the ring of one element every polynomial is zero.
+        if points.is_empty() || ring.from(1).is_zero() {
+            return ModPoly::zero(ring);
+        }
+
+        // Lagrange: p = sum values[i] / m'(points[i]) * m / (x - points[i]),
+        // where m is the product of all x - points[i].
+        let ntt_roots = NttRoots::new(ring, 2 * (points.len() + 1));
+        let tree = SubproductTree::new(ring, &ntt_roots, points);
+        let mut weights = tree.remainders(&ntt_roots, &tree.root().derivative());
+        if ring.batch_inverse(&mut weights).is_err() {
+            panic!("Interpolation points with a non-invertible difference")
+        }
+        let mut level: Vec<ModPoly> = weights
+            .iter()
+            .zip(values)
+            .map(|(w, v)| ModPoly::from_vec(ring, vec![w * v]))
+            .collect();
+
+        // Going up, a node's sum is left_sum * right_product + right_sum * left_product.
+        for products in &tree.levels[..tree.levels.len() - 1] {
+            level = level
+                .chunks(2)
+                .zip(products.chunks(2))
+                .map(|(sums, products)| match (sums, products) {
+                    ([left, right], [left_product, right_product]) => {
+                        let product = |x: &ModPoly<'a>, y: &ModPoly<'a>| {
+                            ModPoly::from_vec(ring, mul(ring, &ntt_roots, &x.coeffs, &y.coeffs))
+                        };
+                        product(left, right_product) + product(right, left_product)
+                    }
+                    _ => sums[0].clone(),
+                })
+                .collect();
+        }
+        debug_assert!(level.len() == 1);
+        level.pop().unwrap()
+    }
+
+    /// Horner's rule.
+    fn horner(&self, x: &Modulo<'a>) -> Modulo<'a> {
+        let mut res = self.ring.from(0);
+        for c in self.coeffs.iter().rev() {
+            res *= x;
+            res += c;
+        }
+        res
+    }
+}
+
+/// Products of `x - points[i]` over ranges of points, arranged in a binary tree.
+struct SubproductTree<'a> {
+    /// `levels[0]` has the linear factors. Each level has the products of adjacent pairs from
+    /// the level below, and the last one unpaired if there is an odd number. The last level has
+    /// the product of all factors.
+    levels: Vec<Vec<ModPoly<'a>>>,
+}
+
+impl<'a> SubproductTree<'a> {
+    /// Build the tree for nonempty `points`, multiplying with `ntt_roots`.
+    ///
+    /// `ntt_roots` should cover products of up to `points.len() + 1` coefficients.
+    fn new(
+        ring: &'a ModuloRing,
+        ntt_roots: &NttRoots<'a>,
+        points: &[Modulo<'a>],
+    ) -> SubproductTree<'a> {
+        debug_assert!(!points.is_empty());
+        let leaves = points
+            .iter()
+            .map(|x| ModPoly::from_vec(ring, vec![-x, ring.from(1)]))
+            .collect();
+        let mut levels: Vec<Vec<ModPoly>> = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => {
+                        ModPoly::from_vec(ring, mul(ring, ntt_roots, &left.coeffs, &right.coeffs))
+                    }
+                    _ => pair[0].clone(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        SubproductTree { levels }
+    }
+
+    /// The product of all the linear factors.
+    fn root(&self) -> &ModPoly<'a> {
+        &self.levels.last().unwrap()[0]
+    }
+
+    /// `p` evaluated at the points: its remainders modulo the linear factors.
+    ///
+    /// `ntt_roots` should cover products of up to twice the length of `p` and of the root.
+    fn remainders(&self, ntt_roots: &NttRoots<'a>, p: &ModPoly<'a>) -> Vec<Modulo<'a>> {
+        let mut rems = vec![p.div_rem_with(ntt_roots, self.root()).1];
+        for products in self.levels.iter().rev().skip(1) {
+            rems = products
+                .iter()
+                .enumerate()
+                .map(|(i, product)| rems[i / 2].div_rem_with(ntt_roots, product).1)
+                .collect();
+        }
+        rems.into_iter()
+            .map(|r| match r.coeffs.into_iter().next() {
+                Some(c) => c,
+                None => r.ring.from(0),
+            })
+            .collect()
+    }
+}
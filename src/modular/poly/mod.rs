@@ -0,0 +1,280 @@
+//! Polynomials with coefficients in a modular ring.
+
+use crate::modular::{convert::IntoModulo, modulo::Modulo, modulo_ring::ModuloRing};
+use alloc::vec::Vec;
+use core::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+mod div;
+mod eval;
+mod mul;
+
+/// A polynomial with coefficients in a [ModuloRing].
+///
+/// Coefficients are stored from the constant term up, without trailing zeros. The zero
+/// polynomial has no coefficients.
+///
+/// Trying to mix polynomials from different rings will cause a panic.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::{modular::{ModPoly, ModuloRing}, ubig};
+/// let ring = ModuloRing::new(&ubig!(7));
+/// // x + 1 and x - 1
+/// let a = ModPoly::new(&ring, [1, 1]);
+/// let b = ModPoly::new(&ring, [-1, 1]);
+/// assert_eq!(&a * &b, ModPoly::new(&ring, [-1, 0, 1]));
+/// assert_eq!(format!("{}", &a * &b), "1*x^2 + 6 (mod 7)");
+/// ```
+#[derive(Clone)]
+pub struct ModPoly<'a> {
+    ring: &'a ModuloRing,
+    /// Coefficients from the constant term up, the last one nonzero.
+    coeffs: Vec<Modulo<'a>>,
+}
+
+impl<'a> ModPoly<'a> {
+    /// Create a polynomial from its coefficients, starting from the constant term.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModPoly, ModuloRing}, ubig};
+    /// let ring = ModuloRing::new(&ubig!(10));
+    /// let p = ModPoly::new(&ring, [3, 0, 12, 20]);
+    /// assert_eq!(p.degree(), Some(2));
+    /// assert_eq!(p.coeffs(), [ring.from(3), ring.from(0), ring.from(2)]);
+    /// ```
+    pub fn new<T: IntoModulo, I: IntoIterator<Item = T>>(
+        ring: &'a ModuloRing,
+        coeffs: I,
+    ) -> ModPoly<'a> {
+        let coeffs = coeffs.into_iter().map(|x| ring.from(x)).collect();
+        ModPoly::from_vec(ring, coeffs)
+    }
+
+    /// Create a polynomial from [Modulo] coefficients, starting from the constant term.
+    ///
+    /// # Panics
+    ///
+    /// Panics if some coefficient is not from the ring `ring`.
+    pub fn from_coeffs(ring: &'a ModuloRing, coeffs: Vec<Modulo<'a>>) -> ModPoly<'a> {
+        for coeff in &coeffs {
+            coeff.check_ring(ring);
+        }
+        ModPoly::from_vec(ring, coeffs)
+    }
+
+    /// The zero polynomial.
+    #[inline]
+    pub fn zero(ring: &'a ModuloRing) -> ModPoly<'a> {
+        ModPoly {
+            ring,
+            coeffs: Vec::new(),
+        }
+    }
+
+    /// The ring of the coefficients.
+    #[inline]
+    pub fn ring(&self) -> &'a ModuloRing {
+        self.ring
+    }
+
+    /// The coefficients, starting from the constant term, without trailing zeros.
+    #[inline]
+    pub fn coeffs(&self) -> &[Modulo<'a>] {
+        &self.coeffs
+    }
+
+    /// The degree, or `None` for the zero polynomial.
+    #[inline]
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// Whether this is the zero polynomial.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// The coefficient of the highest power, or `None` for the zero polynomial.
+    #[inline]
+    pub fn leading_coeff(&self) -> Option<&Modulo<'a>> {
+        self.coeffs.last()
+    }
+
+    /// The formal derivative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::{ModPoly, ModuloRing}, ubig};
+    /// let ring = ModuloRing::new(&ubig!(7));
+    /// let p = ModPoly::new(&ring, [5, 4, 3, 2]);
+    /// assert_eq!(p.derivative(), ModPoly::new(&ring, [4, 6, 6]));
+    /// ```
+    pub fn derivative(&self) -> ModPoly<'a> {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| c * self.ring.from(i))
+            .collect();
+        ModPoly::from_vec(self.ring, coeffs)
+    }
+
+    /// Polynomial from coefficients known to be in `ring`, possibly with trailing zeros.
+    fn from_vec(ring: &'a ModuloRing, mut coeffs: Vec<Modulo<'a>>) -> ModPoly<'a> {
+        while let Some(c) = coeffs.last() {
+            if !c.is_zero() {
+                break;
+            }
+            coeffs.pop();
+        }
+        ModPoly { ring, coeffs }
+    }
+
+    /// Checks that two polynomials are from the same ring.
+    #[inline]
+    fn check_same_ring(&self, other: &ModPoly) {
+        if self.ring != other.ring {
+            Modulo::panic_different_rings();
+        }
+    }
+}
+
+/// Equality within a ring.
+///
+/// # Panics
+///
+/// Panics if the two polynomials are from different rings.
+impl PartialEq for ModPoly<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.check_same_ring(other);
+        self.coeffs == other.coeffs
+    }
+}
+
+impl Eq for ModPoly<'_> {}
+
+impl<'a> Neg for ModPoly<'a> {
+    type Output = ModPoly<'a>;
+
+    #[inline]
+    fn neg(mut self) -> ModPoly<'a> {
+        for c in &mut self.coeffs {
+            *c = -&*c;
+        }
+        self
+    }
+}
+
+impl<'a> Neg for &ModPoly<'a> {
+    type Output = ModPoly<'a>;
+
+    #[inline]
+    fn neg(self) -> ModPoly<'a> {
+        self.clone().neg()
+    }
+}
+
+impl<'a> AddAssign<&ModPoly<'a>> for ModPoly<'a> {
+    fn add_assign(&mut self, rhs: &ModPoly<'a>) {
+        self.check_same_ring(rhs);
+        if self.coeffs.len() < rhs.coeffs.len() {
+            self.coeffs.resize(rhs.coeffs.len(), self.ring.from(0));
+        }
+        add_in_place(&mut self.coeffs, &rhs.coeffs);
+        *self = ModPoly::from_vec(self.ring, core::mem::take(&mut self.coeffs));
+    }
+}
+
+impl<'a> SubAssign<&ModPoly<'a>> for ModPoly<'a> {
+    fn sub_assign(&mut self, rhs: &ModPoly<'a>) {
+        self.check_same_ring(rhs);
+        if self.coeffs.len() < rhs.coeffs.len() {
+            self.coeffs.resize(rhs.coeffs.len(), self.ring.from(0));
+        }
+        sub_in_place(&mut self.coeffs, &rhs.coeffs);
+        *self = ModPoly::from_vec(self.ring, core::mem::take(&mut self.coeffs));
+    }
+}
+
+/// Implement a binary operator and its assignment form for all combinations of owned and
+/// borrowed arguments, given the assignment form taking `&ModPoly`.
+macro_rules! impl_binop {
+    ($trait:ident, $method:ident, $trait_assign:ident, $method_assign:ident) => {
+        impl<'a> $trait<ModPoly<'a>> for ModPoly<'a> {
+            type Output = ModPoly<'a>;
+
+            #[inline]
+            fn $method(mut self, rhs: ModPoly<'a>) -> ModPoly<'a> {
+                self.$method_assign(&rhs);
+                self
+            }
+        }
+
+        impl<'a> $trait<&ModPoly<'a>> for ModPoly<'a> {
+            type Output = ModPoly<'a>;
+
+            #[inline]
+            fn $method(mut self, rhs: &ModPoly<'a>) -> ModPoly<'a> {
+                self.$method_assign(rhs);
+                self
+            }
+        }
+
+        impl<'a> $trait<ModPoly<'a>> for &ModPoly<'a> {
+            type Output = ModPoly<'a>;
+
+            #[inline]
+            fn $method(self, rhs: ModPoly<'a>) -> ModPoly<'a> {
+                self.clone().$method(&rhs)
+            }
+        }
+
+        impl<'a> $trait<&ModPoly<'a>> for &ModPoly<'a> {
+            type Output = ModPoly<'a>;
+
+            #[inline]
+            fn $method(self, rhs: &ModPoly<'a>) -> ModPoly<'a> {
+                self.clone().$method(rhs)
+            }
+        }
+
+        impl<'a> $trait_assign<ModPoly<'a>> for ModPoly<'a> {
+            #[inline]
+            fn $method_assign(&mut self, rhs: ModPoly<'a>) {
+                self.$method_assign(&rhs)
+            }
+        }
+    };
+}
+
+use impl_binop;
+
+impl_binop!(Add, add, AddAssign, add_assign);
+impl_binop!(Sub, sub, SubAssign, sub_assign);
+
+/// a += b
+///
+/// `a` is at least as long as `b`.
+fn add_in_place<'a>(a: &mut [Modulo<'a>], b: &[Modulo<'a>]) {
+    debug_assert!(a.len() >= b.len());
+    for (x, y) in a.iter_mut().zip(b) {
+        *x += y;
+    }
+}
+
+/// a -= b
+///
+/// `a` is at least as long as `b`.
+fn sub_in_place<'a>(a: &mut [Modulo<'a>], b: &[Modulo<'a>]) {
+    debug_assert!(a.len() >= b.len());
+    for (x, y) in a.iter_mut().zip(b) {
+        *x -= y;
+    }
+}
@@ -0,0 +1,212 @@
+//! Polynomial multiplication.
+
+use super::{add_in_place, impl_binop, sub_in_place, ModPoly};
+use crate::{
+    modular::{modulo::Modulo, modulo_ring::ModuloRing},
+    mul::ntt::{self, Transform},
+    ubig::UBig,
+};
+use alloc::{vec, vec::Vec};
+use core::ops::{Mul, MulAssign};
+
+/// If the shorter factor is at least this long, use Karatsuba.
+const MIN_LEN_KARATSUBA: usize = 32;
+
+/// If the shorter factor is at least this long and the ring allows it, use the number-theoretic
+/// transform.
+const MIN_LEN_NTT: usize = 128;
+
+/// Number of candidates to try when looking for a root of unity.
+const NTT_ROOT_CANDIDATES: u32 = 64;
+
+impl<'a> MulAssign<&ModPoly<'a>> for ModPoly<'a> {
+    fn mul_assign(&mut self, rhs: &ModPoly<'a>) {
+        self.check_same_ring(rhs);
+        // The product is shorter than twice the longer factor.
+        let ntt_roots = NttRoots::new(self.ring, 2 * self.coeffs.len().max(rhs.coeffs.len()));
+        let coeffs = mul(self.ring, &ntt_roots, &self.coeffs, &rhs.coeffs);
+        *self = ModPoly::from_vec(self.ring, coeffs);
+    }
+}
+
+impl_binop!(Mul, mul, MulAssign, mul_assign);
+
+/// a * b
+///
+/// The result has `a.len() + b.len() - 1` coefficients, or none if either is empty.
+pub(super) fn mul<'a>(
+    ring: &'a ModuloRing,
+    ntt_roots: &NttRoots<'a>,
+    a: &[Modulo<'a>],
+    b: &[Modulo<'a>],
+) -> Vec<Modulo<'a>> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if b.len() >= MIN_LEN_NTT {
+        if let Some(transform) = ntt_roots.transform(a.len() + b.len() - 1) {
+            return mul_ntt(ring, &transform, a, b);
+        }
+    }
+    mul_karatsuba(ring, a, b)
+}
+
+/// a * b, using schoolbook multiplication for small factors.
+///
+/// `a` is at least as long as `b`.
+fn mul_karatsuba<'a>(ring: &'a ModuloRing, a: &[Modulo<'a>], b: &[Modulo<'a>]) -> Vec<Modulo<'a>> {
+    debug_assert!(a.len() >= b.len());
+    if b.is_empty() {
+        return Vec::new();
+    }
+    if b.len() < MIN_LEN_KARATSUBA {
+        return mul_schoolbook(ring, a, b);
+    }
+
+    let mut res = vec![ring.from(0); a.len() + b.len() - 1];
+    if 2 * b.len() <= a.len() {
+        // Unbalanced: multiply b by chunks of a.
+        for (i, a_chunk) in a.chunks(b.len()).enumerate() {
+            let (x, y) = if a_chunk.len() >= b.len() {
+                (a_chunk, b)
+            } else {
+                (b, a_chunk)
+            };
+            add_in_place(&mut res[i * b.len()..], &mul_karatsuba(ring, x, y));
+        }
+        return res;
+    }
+
+    // a = a_lo + x^half a_hi, b = b_lo + x^half b_hi
+    let half = (a.len() + 1) / 2;
+    let (a_lo, a_hi) = a.split_at(half);
+    let (b_lo, b_hi) = b.split_at(half);
+    let lo = mul_karatsuba(ring, a_lo, b_lo);
+    let hi = if a_hi.len() >= b_hi.len() {
+        mul_karatsuba(ring, a_hi, b_hi)
+    } else {
+        mul_karatsuba(ring, b_hi, a_hi)
+    };
+    // (a_lo + a_hi) (b_lo + b_hi) - lo - hi
+    let mut a_sum = a_lo.to_vec();
+    add_in_place(&mut a_sum, a_hi);
+    let mut b_sum = b_lo.to_vec();
+    add_in_place(&mut b_sum, b_hi);
+    let mut mid = mul_karatsuba(ring, &a_sum, &b_sum);
+    sub_in_place(&mut mid, &lo);
+    sub_in_place(&mut mid, &hi);
+
+    add_in_place(&mut res, &lo);
+    // The top coefficients of mid may be zeros past the end of res.
+    let mid_len = mid.len().min(res.len() - half);
+    add_in_place(&mut res[half..], &mid[..mid_len]);
+    add_in_place(&mut res[2 * half..], &hi);
+    res
+}
+
+/// a * b, nonempty.
+fn mul_schoolbook<'a>(ring: &'a ModuloRing, a: &[Modulo<'a>], b: &[Modulo<'a>]) -> Vec<Modulo<'a>> {
+    debug_assert!(!a.is_empty() && !b.is_empty());
+    let mut res = vec![ring.from(0); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (r, y) in res[i..].iter_mut().zip(b) {
+            *r += x * y;
+        }
+    }
+    res
+}
+
+/// a * b, where the product fits in the transform.
+fn mul_ntt<'a>(
+    ring: &'a ModuloRing,
+    transform: &Transform<Modulo<'a>>,
+    a: &[Modulo<'a>],
+    b: &[Modulo<'a>],
+) -> Vec<Modulo<'a>> {
+    let res_len = a.len() + b.len() - 1;
+    let len = 1 << transform.order();
+    debug_assert!(res_len <= len);
+    let zero = ring.from(0);
+    let mut fa = a.to_vec();
+    fa.resize(len, zero.clone());
+    let mut fb = b.to_vec();
+    fb.resize(len, zero);
+    transform.forward(&mut fa);
+    transform.forward(&mut fb);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x *= y;
+    }
+    transform.inverse(&mut fa);
+    fa.truncate(res_len);
+    fa
+}
+
+/// Roots of unity for number-theoretic transforms in a ring, up to some order.
+///
+/// Finding a root takes up to `NTT_ROOT_CANDIDATES` exponentiations, so algorithms that multiply
+/// many times find one root of the largest order they need and pass it down. Roots of smaller
+/// orders are its powers. Products too long for the roots are multiplied without the transform.
+pub(super) struct NttRoots<'a> {
+    ring: &'a ModuloRing,
+    /// Transforms of length up to `2^max_order` are available. 0 if there are none.
+    max_order: u32,
+    /// `w` of order `2^max_order` such that `w^(2^(max_order-1)) = -1`.
+    root: Modulo<'a>,
+    /// `2^-max_order`
+    len_inverse: Modulo<'a>,
+}
+
+impl<'a> NttRoots<'a> {
+    /// Roots for products of up to `len` coefficients.
+    ///
+    /// If the ring only has roots of smaller orders, those are used for shorter products. No
+    /// root is searched for if the products are too short for the transform.
+    pub(super) fn new(ring: &'a ModuloRing, len: usize) -> NttRoots<'a> {
+        let min_order = (2 * MIN_LEN_NTT - 1).next_power_of_two().trailing_zeros();
+        let order = len.next_power_of_two().trailing_zeros();
+        let modulus_minus_one = ring.modulus() - UBig::from_word(1);
+        let max_order = match modulus_minus_one.trailing_zeros() {
+            Some(zeros) => (order as usize).min(zeros) as u32,
+            None => 0,
+        };
+        if max_order >= min_order {
+            // For a candidate `g^cofactor` the condition `w^(2^(max_order-1)) = -1` is the same
+            // as for its powers of smaller orders, so only the largest order is searched.
+            let cofactor = modulus_minus_one >> max_order as usize;
+            let candidates = (2..NTT_ROOT_CANDIDATES + 2).map(|g| ring.from(g).pow(&cofactor));
+            let root = ntt::find_root(max_order, &ring.from(-1), candidates);
+            let len_inverse = ring
+                .from(UBig::from_word(1) << max_order as usize)
+                .inverse();
+            if let (Some(root), Some(len_inverse)) = (root, len_inverse) {
+                return NttRoots {
+                    ring,
+                    max_order,
+                    root,
+                    len_inverse,
+                };
+            }
+        }
+        NttRoots {
+            ring,
+            max_order: 0,
+            root: ring.from(1),
+            len_inverse: ring.from(1),
+        }
+    }
+
+    /// A transform long enough for `len` coefficients, if there is a root of that order.
+    fn transform(&self, len: usize) -> Option<Transform<Modulo<'a>>> {
+        let order = len.next_power_of_two().trailing_zeros().max(1);
+        if order > self.max_order {
+            return None;
+        }
+        // Each squaring halves the order of the root.
+        let mut root = self.root.clone();
+        let mut len_inverse = self.len_inverse.clone();
+        let two = self.ring.from(2);
+        for _ in order..self.max_order {
+            root = &root * &root;
+            len_inverse *= &two;
+        }
+        Some(Transform::new(order, &root, self.ring.from(1), len_inverse))
+    }
+}
@@ -4,13 +4,16 @@ use crate::{
     math,
     memory::{self, MemoryAllocation},
     modular::{
+        barrett::Barrett,
         modulo::{Modulo, ModuloLarge, ModuloRepr, ModuloSmall, ModuloSmallRaw},
-        modulo_ring::ModuloRingSmall,
+        modulo_ring::{ModuloRing, ModuloRingSmall},
     },
     primitive::{double_word, split_double_word, PrimitiveUnsigned, WORD_BITS, WORD_BITS_USIZE},
+    shift,
     sign::Sign::*,
     ubig::{Repr::*, UBig},
 };
+use alloc::{vec, vec::Vec};
 
 impl<'a> Modulo<'a> {
     /// Exponentiation.
@@ -58,6 +61,131 @@ impl<'a> Modulo<'a> {
             },
         }
     }
+
+    /// Exponentiation in constant time, for secret exponents.
+    ///
+    /// Uses a fixed window with the table lookup done by scanning all entries with masks. The
+    /// sequence of operations only depends on the ring and on the length of `exp` rounded up to
+    /// the length of the modulus: there are no branches or early exits depending on the bits of
+    /// the exponent or on the values. Exponents longer than the modulus leak their length, so
+    /// reduce secret exponents first.
+    ///
+    /// The guarantee covers the arithmetic implemented in this crate, not what the compiler or
+    /// the hardware may do with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::ModuloRing, ubig};
+    /// let p = ubig!(2).pow(607) - ubig!(1);
+    /// let ring = ModuloRing::new(&p);
+    /// let a = ring.from(123);
+    /// let e = ubig!(_0x123456789abcdef0123456789abcdef);
+    /// assert_eq!(a.pow_ct(&e), a.pow(&e));
+    /// ```
+    #[inline]
+    pub fn pow_ct(&self, exp: &UBig) -> Modulo<'a> {
+        match self.repr() {
+            ModuloRepr::Small(self_small) => self_small.pow_ct(exp).into(),
+            ModuloRepr::Large(self_large) => self_large.pow_ct(exp).into(),
+        }
+    }
+}
+
+impl ModuloRing {
+    /// Product of powers `base_1^exp_1 * base_2^exp_2 * ...`.
+    ///
+    /// The squarings are shared between all the terms (Straus's method), so this is faster than
+    /// computing the powers separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::{modular::ModuloRing, ubig};
+    /// let ring = ModuloRing::new(&ubig!(1000000007));
+    /// let (g, h) = (ring.from(5), ring.from(7));
+    /// let (a, b) = (ubig!(123456789), ubig!(987654321));
+    /// assert_eq!(ring.multi_pow(&[(&g, &a), (&h, &b)]), g.pow(&a) * h.pow(&b));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if some base is not from this ring.
+    pub fn multi_pow<'a>(&'a self, terms: &[(&Modulo<'a>, &UBig)]) -> Modulo<'a> {
+        for (base, _) in terms {
+            base.check_ring(self);
+        }
+
+        // For each term: odd powers of the base, and the exponent split into windows.
+        let terms: Vec<_> = terms
+            .iter()
+            .map(|&(base, exp)| {
+                let window_len = ModuloLarge::choose_pow_window_len(exp.bit_len());
+                (odd_powers(base, window_len), odd_windows(exp, window_len))
+            })
+            .collect();
+        let bit_len = terms
+            .iter()
+            .filter_map(|(_, windows)| windows.first().map(|&(bit, _)| bit + 1))
+            .max()
+            .unwrap_or(0);
+
+        let mut res = self.from(1);
+        let mut next = vec![0; terms.len()];
+        for bit in (0..bit_len).rev() {
+            res.square_in_place();
+            for ((table, windows), next) in terms.iter().zip(next.iter_mut()) {
+                if let Some(&(window_bit, idx)) = windows.get(*next) {
+                    if window_bit == bit {
+                        res *= &table[idx];
+                        *next += 1;
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+/// `[base, base^3, base^5, ..., base^(2^window_len - 1)]`.
+fn odd_powers<'a>(base: &Modulo<'a>, window_len: u32) -> Vec<Modulo<'a>> {
+    let len = 1 << (window_len - 1);
+    let mut powers = Vec::with_capacity(len);
+    powers.push(base.clone());
+    if len > 1 {
+        let mut square = base.clone();
+        square.square_in_place();
+        for i in 1..len {
+            let power = &powers[i - 1] * &square;
+            powers.push(power);
+        }
+    }
+    powers
+}
+
+/// Split `exp` into odd windows of at most `window_len` bits.
+///
+/// Returns `(bit, idx)` pairs from the top: `exp` is the sum of `(2 * idx + 1) << bit`.
+fn odd_windows(exp: &UBig, window_len: u32) -> Vec<(usize, usize)> {
+    let mut windows = Vec::new();
+    let mut bit = exp.bit_len();
+    while bit != 0 {
+        bit -= 1;
+        if !exp.bit(bit) {
+            continue;
+        }
+        // The window is exp[low..=bit], with the lowest bit set.
+        let mut low = bit.saturating_sub(window_len as usize - 1);
+        while !exp.bit(low) {
+            low += 1;
+        }
+        let window = (low..=bit)
+            .rev()
+            .fold(0, |w, i| 2 * w + exp.bit(i) as usize);
+        windows.push((low, window >> 1));
+        bit = low;
+    }
+    windows
 }
 
 impl ModuloSmallRaw {
@@ -96,7 +224,7 @@ impl ModuloSmallRaw {
 impl<'a> ModuloSmall<'a> {
     /// Exponentiation.
     #[inline]
-    fn pow(&self, exp: &UBig) -> ModuloSmall<'a> {
+    pub(crate) fn pow(&self, exp: &UBig) -> ModuloSmall<'a> {
         match exp.repr() {
             // self^0 == 1
             Small(0) => ModuloSmall::from_ubig(&UBig::from_word(1), self.ring()),
@@ -125,6 +253,35 @@ impl<'a> ModuloSmall<'a> {
     }
 }
 
+impl<'a> ModuloSmall<'a> {
+    /// Exponentiation in constant time.
+    fn pow_ct(&self, exp: &UBig) -> ModuloSmall<'a> {
+        let ring = self.ring();
+        let modulus_bits = (WORD_BITS - ring.shift()) as usize;
+        let (exp_words, exp_bits) = ct_exp_words(exp, modulus_bits, CT_WINDOW_LEN_SMALL);
+
+        // table[i] = self^i
+        let mut table = [ModuloSmallRaw::from_word(1, ring); 1 << CT_WINDOW_LEN_SMALL];
+        for i in 1..table.len() {
+            table[i] = table[i - 1].mul(self.raw(), ring);
+        }
+
+        let mut val = table[0];
+        for bit in (0..exp_bits).step_by(CT_WINDOW_LEN_SMALL).rev() {
+            for _ in 0..CT_WINDOW_LEN_SMALL {
+                val = val.mul(val, ring);
+            }
+            let digit = ct_window(&exp_words, bit, CT_WINDOW_LEN_SMALL);
+            let mut entry = 0;
+            for (i, x) in table.iter().enumerate() {
+                entry |= x.normalized() & ct_eq_mask(i, digit);
+            }
+            val = val.mul(ModuloSmallRaw::from_normalized(entry), ring);
+        }
+        ModuloSmall::new(val, ring)
+    }
+}
+
 impl<'a> ModuloLarge<'a> {
     fn pow(&self, exp: &UBig) -> ModuloLarge<'a> {
         match exp.repr() {
@@ -132,7 +289,10 @@ impl<'a> ModuloLarge<'a> {
             Small(0) => ModuloLarge::from_ubig(UBig::from_word(1), self.ring()),
             // self^1 == self
             Small(1) => self.clone(),
-            _ => self.pow_nontrivial(exp),
+            _ => match self.ring().crt() {
+                Some(crt) => crt.pow(self, exp),
+                None => self.pow_nontrivial(exp),
+            },
         }
     }
 
@@ -158,7 +318,7 @@ impl<'a> ModuloLarge<'a> {
 
         // val = self^2
         let mut val = self.clone();
-        val.mul_in_place(self, &mut memory);
+        val.square_in_place(&mut memory);
 
         // self^(2*i+1) = self^(2*i-1) * val
         for i in 1..(1 << (window_len - 1)) {
@@ -225,9 +385,88 @@ impl<'a> ModuloLarge<'a> {
         val
     }
 
+    /// Exponentiation in constant time.
+    ///
+    /// Odd moduli use Montgomery multiplication, others Barrett reduction.
+    fn pow_ct(&self, exp: &UBig) -> ModuloLarge<'a> {
+        let ring = self.ring();
+        match ring.montgomery() {
+            Some(montgomery) => self.pow_ct_with(exp, montgomery.mul_ct_len(), |a, b, t| {
+                montgomery.mul_ct(a, b, t)
+            }),
+            None => {
+                let barrett = Barrett::new(ring.normalized_modulus(), ring.shift());
+                self.pow_ct_with(exp, barrett.mul_ct_len(), |a, b, t| barrett.mul_ct(a, b, t))
+            }
+        }
+    }
+
+    /// Exponentiation in constant time, given a constant-time product `mul_ct(a, b, t)` that
+    /// stores the result in `t[..n]` and needs `mul_ct_len` temporary words.
+    ///
+    /// The values are not normalized. With Montgomery multiplication they stay in Montgomery
+    /// representation.
+    fn pow_ct_with<F>(&self, exp: &UBig, mul_ct_len: usize, mul_ct: F) -> ModuloLarge<'a>
+    where
+        F: Fn(&[Word], &[Word], &mut [Word]),
+    {
+        let ring = self.ring();
+        let n = ring.normalized_modulus().len();
+        let shift = ring.shift();
+        let modulus_bits = n * WORD_BITS_USIZE - shift as usize;
+        let window_len = if modulus_bits > CT_MIN_BITS_WINDOW_LEN_LARGE {
+            CT_WINDOW_LEN_LARGE
+        } else {
+            CT_WINDOW_LEN_SMALL
+        };
+        let (exp_words, exp_bits) = ct_exp_words(exp, modulus_bits, window_len);
+
+        let table_len = 1 << window_len;
+        let memory_requirement = memory::array_layout::<Word>((table_len + 2) * n + mul_ct_len);
+        let mut allocation = MemoryAllocation::new(memory_requirement);
+        let mut memory = allocation.memory();
+        let (table, mut memory) = memory.allocate_slice_fill::<Word>(table_len * n, 0);
+        let (val, mut memory) = memory.allocate_slice_fill::<Word>(n, 0);
+        let (entry, mut memory) = memory.allocate_slice_fill::<Word>(n, 0);
+        let (t, _) = memory.allocate_slice_fill::<Word>(mul_ct_len, 0);
+
+        // table[i] = self^i
+        let one = ModuloLarge::from_ubig(UBig::from_word(1), ring);
+        table[..n].copy_from_slice(one.normalized_value());
+        val.copy_from_slice(self.normalized_value());
+        shift::shr_in_place(&mut table[..n], shift);
+        shift::shr_in_place(val, shift);
+        for i in 1..table_len {
+            mul_ct(&table[(i - 1) * n..i * n], val, t);
+            table[i * n..(i + 1) * n].copy_from_slice(&t[..n]);
+        }
+
+        val.copy_from_slice(&table[..n]);
+        for bit in (0..exp_bits).step_by(window_len).rev() {
+            for _ in 0..window_len {
+                mul_ct(val, val, t);
+                val.copy_from_slice(&t[..n]);
+            }
+            let digit = ct_window(&exp_words, bit, window_len);
+            entry.fill(0);
+            for (i, x) in table.chunks(n).enumerate() {
+                let mask = ct_eq_mask(i, digit);
+                for (e, &x) in entry.iter_mut().zip(x) {
+                    *e |= x & mask;
+                }
+            }
+            mul_ct(val, entry, t);
+            val.copy_from_slice(&t[..n]);
+        }
+
+        let carry = shift::shl_in_place(val, shift);
+        debug_assert!(carry == 0);
+        ModuloLarge::new(val.to_vec(), ring)
+    }
+
     /// Choose the optimal window size for n-bit exponents.
     /// 1 <= window_size < min(WORD_BITS, usize::BIT_SIZE) inclusive.
-    fn choose_pow_window_len(n: usize) -> u32 {
+    pub(crate) fn choose_pow_window_len(n: usize) -> u32 {
         // This won't overflow because cost(3) is already approximately usize::MAX / 4
         // and it can only grow by a factor of 2.
         let cost = |window_size| (1usize << (window_size - 1)) - 1 + n / (window_size as usize + 1);
@@ -245,6 +484,53 @@ impl<'a> ModuloLarge<'a> {
     }
 }
 
+/// Window length for constant-time exponentiation.
+const CT_WINDOW_LEN_SMALL: usize = 4;
+
+/// Window length for constant-time exponentiation with moduli longer than
+/// `CT_MIN_BITS_WINDOW_LEN_LARGE` bits.
+const CT_WINDOW_LEN_LARGE: usize = 5;
+
+const CT_MIN_BITS_WINDOW_LEN_LARGE: usize = 512;
+
+/// Words of `exp` and the number of bits to process.
+///
+/// The number of bits is at least `min_bits` and a multiple of `window_len`. The words are
+/// zero-padded with one extra word for `ct_window`.
+///
+/// The buffer is allocated with its final length, which only depends on `min_bits` unless `exp`
+/// is longer, so that the allocation doesn't depend on the exponent.
+fn ct_exp_words(exp: &UBig, min_bits: usize, window_len: usize) -> (Vec<Word>, usize) {
+    let bits = exp.bit_len().max(min_bits);
+    let bits = (bits + window_len - 1) / window_len * window_len;
+    let mut words = vec![0; bits / WORD_BITS_USIZE + 2];
+    let exp_words = exp.as_words();
+    words[..exp_words.len()].copy_from_slice(exp_words);
+    (words, bits)
+}
+
+/// Bits `bit..bit + window_len` of `words`, without branches.
+///
+/// `words` must be long enough.
+fn ct_window(words: &[Word], bit: usize, window_len: usize) -> usize {
+    let (word_idx, bit_idx) = (bit / WORD_BITS_USIZE, (bit % WORD_BITS_USIZE) as u32);
+    // Shift in two steps, so that a zero bit_idx doesn't shift by WORD_BITS.
+    let window = words[word_idx] >> bit_idx | words[word_idx + 1] << (WORD_BITS - 1 - bit_idx) << 1;
+    (window & math::ones::<Word>(window_len as u32)) as usize
+}
+
+/// All ones if `a == b`, zero otherwise, without branches.
+///
+/// The volatile read hides the value from the optimizer, so that it can't turn the masking in
+/// the table lookup back into a branch on `a == b`.
+fn ct_eq_mask(a: usize, b: usize) -> Word {
+    let x = (a ^ b) as Word;
+    let nonzero = (x | x.wrapping_neg()) >> (WORD_BITS - 1);
+    // SAFETY: reading a local variable.
+    let nonzero = unsafe { core::ptr::read_volatile(&nonzero) };
+    nonzero.wrapping_sub(1)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,9 +3,12 @@
 use crate::{
     add,
     arch::word::{SignedWord, Word},
+    div,
     memory::Memory,
     mul,
-    sign::Sign,
+    primitive::WORD_BITS,
+    shift,
+    sign::Sign::{self, *},
 };
 
 /// c += sign * a * b
@@ -47,3 +50,81 @@ where
     }
     carry
 }
+
+// Toom-Cook interpolation works with fixed-length two's complement numbers. All intermediate
+// values are known to fit, so overflows out of the fixed length are ignored.
+
+/// Is a two's complement number negative?
+fn is_negative(words: &[Word]) -> bool {
+    words.last().map_or(false, |w| w >> (WORD_BITS - 1) != 0)
+}
+
+/// Negate a two's complement number in place.
+pub(crate) fn negate_in_place(words: &mut [Word]) {
+    for word in words.iter_mut() {
+        *word = !*word;
+    }
+    let _ = add::add_one_in_place(words);
+}
+
+/// words = sign * a * b as a two's complement number.
+///
+/// words must be zero initially.
+pub(crate) fn signed_mul_same_len(
+    words: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) {
+    let n = a.len();
+    debug_assert!(b.len() == n && words.len() >= 2 * n);
+    let overflow = mul::add_signed_mul_same_len(&mut words[..2 * n], Positive, a, b, memory);
+    assert!(overflow == 0);
+    if sign == Negative {
+        negate_in_place(words);
+    }
+}
+
+/// Divide a two's complement number by 2^shift in place, 0 < shift < WORD_BITS.
+///
+/// The division must be exact.
+pub(crate) fn div_exact_signed_by_pow2_in_place(words: &mut [Word], shift: u32) {
+    let negative = is_negative(words);
+    let rem = shift::shr_in_place(words, shift);
+    debug_assert!(rem == 0);
+    if negative {
+        *words.last_mut().unwrap() |= !(Word::MAX >> shift);
+    }
+}
+
+/// Divide a two's complement number by a `Word` in place.
+///
+/// The division must be exact.
+pub(crate) fn div_exact_signed_by_word_in_place(words: &mut [Word], rhs: Word) {
+    let negative = is_negative(words);
+    if negative {
+        negate_in_place(words);
+    }
+    let rem = div::div_by_word_in_place(words, rhs);
+    debug_assert!(rem == 0);
+    if negative {
+        negate_in_place(words);
+    }
+}
+
+/// c[offset..] += sign * coeff
+///
+/// coeff may have leading zeros beyond the end of c.
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_coefficient(
+    c: &mut [Word],
+    offset: usize,
+    sign: Sign,
+    coeff: &[Word],
+) -> SignedWord {
+    let len = coeff.iter().rposition(|&w| w != 0).map_or(0, |i| i + 1);
+    add::add_signed_in_place(&mut c[offset..], sign, &coeff[..len])
+}
@@ -127,3 +127,61 @@ pub(crate) fn add_signed_mul_same_len(
     assert!(carry.abs() <= 1);
     carry
 }
+
+/// c += sign * a^2
+/// Karatsuba method: O(n^1.59).
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_sqr(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    let n = a.len();
+    debug_assert!(c.len() == 2 * n);
+    debug_assert!(n >= MIN_LEN);
+
+    let mid = (n + 1) / 2;
+
+    let (a_lo, a_hi) = a.split_at(mid);
+    // Result = a_lo^2 + a_hi^2 * Word^(2mid)
+    //        + (a_lo^2 + a_hi^2 - (a_lo-a_hi)^2) * Word^mid
+    let mut carry: SignedWord = 0;
+    let mut carry_c0: SignedWord = 0; // 2*mid
+    let mut carry_c1: SignedWord = 0; // 3*mid
+
+    {
+        // c_0 += a_lo^2
+        // c_1 += a_lo^2
+        let (c_lo, mut memory) = memory.allocate_slice_fill::<Word>(2 * mid, 0);
+        let overflow = mul::add_signed_sqr(c_lo, Positive, a_lo, &mut memory);
+        assert!(overflow == 0);
+        carry_c0 += add::add_signed_same_len_in_place(&mut c[..2 * mid], sign, c_lo);
+        carry_c1 += add::add_signed_same_len_in_place(&mut c[mid..3 * mid], sign, c_lo);
+    }
+    {
+        // c_2 += a_hi^2
+        // c_1 += a_hi^2
+        let (c_hi, mut memory) = memory.allocate_slice_fill::<Word>(2 * (n - mid), 0);
+        let overflow = mul::add_signed_sqr(c_hi, Positive, a_hi, &mut memory);
+        assert!(overflow == 0);
+        carry += add::add_signed_same_len_in_place(&mut c[2 * mid..], sign, c_hi);
+        carry_c1 += add::add_signed_in_place(&mut c[mid..3 * mid], sign, c_hi);
+    }
+    {
+        // c1 -= (a_lo - a_hi)^2
+        let (a_diff, mut memory) = memory.allocate_slice_copy(a_lo);
+        let _ = add::sub_in_place_with_sign(a_diff, a_hi);
+
+        carry_c1 += mul::add_signed_sqr(&mut c[mid..3 * mid], -sign, a_diff, &mut memory);
+    }
+
+    // Propagate carries.
+    carry_c1 += add::add_signed_word_in_place(&mut c[2 * mid..3 * mid], carry_c0);
+    carry += add::add_signed_word_in_place(&mut c[3 * mid..], carry_c1);
+
+    assert!(carry.abs() <= 1);
+    carry
+}
@@ -2,8 +2,11 @@
 
 use crate::{
     add,
-    arch::word::{SignedWord, Word},
-    memory::Memory,
+    arch::{
+        self,
+        word::{SignedWord, Word},
+    },
+    memory::{self, Memory},
     primitive::{double_word, extend_word, split_double_word},
     sign::Sign,
 };
@@ -12,19 +15,29 @@ use core::mem;
 use static_assertions::const_assert;
 
 /// If smaller length <= MAX_LEN_SIMPLE, simple multiplication can be used.
-const MAX_LEN_SIMPLE: usize = 24;
+const MAX_LEN_SIMPLE: usize = arch::thresholds::MUL_MAX_LEN_SIMPLE;
 const_assert!(MAX_LEN_SIMPLE <= simple::MAX_SMALLER_LEN);
 const_assert!(MAX_LEN_SIMPLE + 1 >= karatsuba::MIN_LEN);
+const_assert!(MAX_LEN_SIMPLE <= simple::MAX_LEN_SQR);
 
 /// If smaller length <= this, Karatsuba multiplication can be used.
-const MAX_LEN_KARATSUBA: usize = 192;
+const MAX_LEN_KARATSUBA: usize = arch::thresholds::MUL_MAX_LEN_KARATSUBA;
 const_assert!(MAX_LEN_KARATSUBA + 1 >= toom_3::MIN_LEN);
+const_assert!(MAX_LEN_KARATSUBA + 1 >= toom_32::MIN_LEN);
+const_assert!(MAX_LEN_KARATSUBA + 1 >= toom_42::MIN_LEN);
+
+/// If smaller length <= this, Toom-3 multiplication can be used.
+const MAX_LEN_TOOM_3: usize = arch::thresholds::MUL_MAX_LEN_TOOM_3;
+const_assert!(MAX_LEN_TOOM_3 + 1 >= toom_4::MIN_LEN);
 
 mod helpers;
 mod karatsuba;
 pub(crate) mod ntt;
 mod simple;
 mod toom_3;
+mod toom_32;
+mod toom_4;
+mod toom_42;
 
 /// Multiply a word sequence by a `Word` in place.
 ///
@@ -38,33 +51,17 @@ pub(crate) fn mul_word_in_place(words: &mut [Word], rhs: Word) -> Word {
 ///
 /// Returns carry.
 #[must_use]
-pub(crate) fn mul_word_in_place_with_carry(words: &mut [Word], rhs: Word, mut carry: Word) -> Word {
-    for a in words {
-        // a * b + carry <= MAX * MAX + MAX < DoubleWord::MAX
-        let (v_lo, v_hi) =
-            split_double_word(extend_word(*a) * extend_word(rhs) + extend_word(carry));
-        *a = v_lo;
-        carry = v_hi;
-    }
-    carry
+pub(crate) fn mul_word_in_place_with_carry(words: &mut [Word], rhs: Word, carry: Word) -> Word {
+    arch::mul::mul_word_in_place_with_carry(words, rhs, carry)
 }
 
 /// words += mult * rhs
 ///
 /// Returns carry.
 #[must_use]
-fn add_mul_word_same_len_in_place(words: &mut [Word], mult: Word, rhs: &[Word]) -> Word {
+pub(crate) fn add_mul_word_same_len_in_place(words: &mut [Word], mult: Word, rhs: &[Word]) -> Word {
     assert!(words.len() == rhs.len());
-    let mut carry: Word = 0;
-    for (a, b) in words.iter_mut().zip(rhs.iter()) {
-        // a + mult * b + carry <= MAX * MAX + 2 * MAX <= DoubleWord::MAX
-        let (v_lo, v_hi) = split_double_word(
-            extend_word(*a) + extend_word(carry) + extend_word(mult) * extend_word(*b),
-        );
-        *a = v_lo;
-        carry = v_hi;
-    }
-    carry
+    arch::mul::add_mul_word_same_len_in_place(words, mult, rhs)
 }
 
 /// words += mult * rhs
@@ -114,7 +111,12 @@ pub(crate) fn memory_requirement_up_to(_total_len: usize, smaller_len: usize) ->
     } else if smaller_len <= MAX_LEN_KARATSUBA {
         karatsuba::memory_requirement_up_to(smaller_len)
     } else {
-        toom_3::memory_requirement_up_to(smaller_len)
+        let balanced = if smaller_len <= MAX_LEN_TOOM_3 {
+            toom_3::memory_requirement_up_to(smaller_len)
+        } else {
+            toom_4::memory_requirement_up_to(smaller_len)
+        };
+        memory::max_layout(balanced, toom_42::memory_requirement_up_to(smaller_len))
     }
 }
 
@@ -136,6 +138,10 @@ pub(crate) fn add_signed_mul<'a>(
 ) -> SignedWord {
     debug_assert!(c.len() == a.len() + b.len());
 
+    if is_same_slice(a, b) {
+        return add_signed_sqr(c, sign, a, memory);
+    }
+
     if a.len() < b.len() {
         mem::swap(&mut a, &mut b);
     }
@@ -144,8 +150,16 @@ pub(crate) fn add_signed_mul<'a>(
         simple::add_signed_mul(c, sign, a, b, memory)
     } else if b.len() <= MAX_LEN_KARATSUBA {
         karatsuba::add_signed_mul(c, sign, a, b, memory)
+    } else if 4 * a.len() < 5 * b.len() {
+        if b.len() <= MAX_LEN_TOOM_3 {
+            toom_3::add_signed_mul(c, sign, a, b, memory)
+        } else {
+            toom_4::add_signed_mul(c, sign, a, b, memory)
+        }
+    } else if 4 * a.len() < 7 * b.len() {
+        toom_32::add_signed_mul(c, sign, a, b, memory)
     } else {
-        toom_3::add_signed_mul(c, sign, a, b, memory)
+        toom_42::add_signed_mul(c, sign, a, b, memory)
     }
 }
 
@@ -163,11 +177,46 @@ pub(crate) fn add_signed_mul_same_len(
     let n = a.len();
     debug_assert!(b.len() == n && c.len() == 2 * n);
 
+    if is_same_slice(a, b) {
+        return add_signed_sqr(c, sign, a, memory);
+    }
+
     if n <= MAX_LEN_SIMPLE {
         simple::add_signed_mul_same_len(c, sign, a, b, memory)
     } else if n <= MAX_LEN_KARATSUBA {
         karatsuba::add_signed_mul_same_len(c, sign, a, b, memory)
-    } else {
+    } else if n <= MAX_LEN_TOOM_3 {
         toom_3::add_signed_mul_same_len(c, sign, a, b, memory)
+    } else {
+        toom_4::add_signed_mul_same_len(c, sign, a, b, memory)
+    }
+}
+
+/// c += sign * a^2
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_sqr(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    let n = a.len();
+    debug_assert!(c.len() == 2 * n);
+
+    if n <= MAX_LEN_SIMPLE {
+        simple::add_signed_sqr(c, sign, a, memory)
+    } else if n <= MAX_LEN_KARATSUBA {
+        karatsuba::add_signed_sqr(c, sign, a, memory)
+    } else if n <= MAX_LEN_TOOM_3 {
+        toom_3::add_signed_sqr(c, sign, a, memory)
+    } else {
+        toom_4::add_signed_sqr(c, sign, a, memory)
     }
 }
+
+/// Do `a` and `b` refer to the same memory? Then multiplication is squaring.
+fn is_same_slice(a: &[Word], b: &[Word]) -> bool {
+    a.as_ptr() == b.as_ptr() && a.len() == b.len()
+}
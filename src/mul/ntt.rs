@@ -1,12 +1,21 @@
 //! Number-theoretic multiplication algorithm.
+//!
+//! Also the radix-2 transform itself, generic over the ring element type, shared by the public
+//! [ntt](crate::ntt) module and polynomial multiplication.
 
 use crate::{
     arch::{
         ntt::{MAX_ORDER, PRIMES},
         word::Word,
     },
-    modular::{modulo::ModuloSmallRaw, modulo_ring::ModuloRingSmall},
+    modular::{
+        modulo::{Modulo, ModuloSmallRaw},
+        modulo_ring::ModuloRingSmall,
+        ConstModulo,
+    },
 };
+use alloc::vec::Vec;
+use core::ops::AddAssign;
 
 /// The number of prime factors in the ring.
 pub(crate) const NUM_PRIMES: usize = 3;
@@ -102,6 +111,173 @@ const fn generate_roots(max_order_root: RingElement) -> RootTable {
     table
 }
 
+/// Ring elements that a [Transform] works on.
+///
+/// Values such as `Modulo` only implement arithmetic by reference efficiently, so the
+/// operations take references.
+pub(crate) trait NttElement: Clone + AddAssign + PartialEq {
+    /// `self * rhs`
+    fn mul_ref(&self, rhs: &Self) -> Self;
+
+    /// `self - rhs`
+    fn sub_ref(&self, rhs: &Self) -> Self;
+
+    /// `-self`
+    fn neg_ref(&self) -> Self;
+}
+
+impl<'a> NttElement for Modulo<'a> {
+    #[inline]
+    fn mul_ref(&self, rhs: &Modulo<'a>) -> Modulo<'a> {
+        self * rhs
+    }
+
+    #[inline]
+    fn sub_ref(&self, rhs: &Modulo<'a>) -> Modulo<'a> {
+        self - rhs
+    }
+
+    #[inline]
+    fn neg_ref(&self) -> Modulo<'a> {
+        -self
+    }
+}
+
+impl<const M: u64> NttElement for ConstModulo<M> {
+    #[inline]
+    fn mul_ref(&self, rhs: &ConstModulo<M>) -> ConstModulo<M> {
+        *self * *rhs
+    }
+
+    #[inline]
+    fn sub_ref(&self, rhs: &ConstModulo<M>) -> ConstModulo<M> {
+        *self - *rhs
+    }
+
+    #[inline]
+    fn neg_ref(&self) -> ConstModulo<M> {
+        -*self
+    }
+}
+
+/// Precomputed roots of unity for transforms of length `2^order`.
+///
+/// The transform of `a` is `A[j] = sum a[i] w^(i j)` for a root of unity `w` of order `2^order`.
+/// Both the input and the output are in natural order.
+pub(crate) struct Transform<T> {
+    order: u32,
+    /// `roots[i] = w^i` for `i < 2^(order-1)`.
+    roots: Vec<T>,
+    /// `inverse_roots[i] = w^-i`
+    inverse_roots: Vec<T>,
+    /// `2^-order`
+    len_inverse: T,
+}
+
+impl<T: NttElement> Transform<T> {
+    /// Transforms of length `2^order` using `root`, which has order `2^order` and
+    /// `root^(2^(order-1)) = -1`.
+    pub(crate) fn new(order: u32, root: &T, one: T, len_inverse: T) -> Transform<T> {
+        let half_len = (1usize << order) / 2;
+        let mut roots = Vec::with_capacity(half_len);
+        if half_len != 0 {
+            roots.push(one.clone());
+        }
+        for i in 1..half_len {
+            let power = roots[i - 1].mul_ref(root);
+            roots.push(power);
+        }
+        // w^-i = w^(2^order - i) = -w^(2^(order-1) - i)
+        let mut inverse_roots = Vec::with_capacity(half_len);
+        if half_len != 0 {
+            inverse_roots.push(one);
+        }
+        inverse_roots.extend((1..half_len).map(|i| roots[half_len - i].neg_ref()));
+        Transform {
+            order,
+            roots,
+            inverse_roots,
+            len_inverse,
+        }
+    }
+
+    /// The transform length is `2^order`.
+    #[inline]
+    pub(crate) fn order(&self) -> u32 {
+        self.order
+    }
+
+    /// Forward transform in place.
+    ///
+    /// Panics if `values.len()` is not `2^order`.
+    pub(crate) fn forward(&self, values: &mut [T]) {
+        self.transform(values, &self.roots);
+    }
+
+    /// Inverse transform in place, including the division by the length.
+    ///
+    /// Panics if `values.len()` is not `2^order`.
+    pub(crate) fn inverse(&self, values: &mut [T]) {
+        self.transform(values, &self.inverse_roots);
+        for x in values {
+            *x = x.mul_ref(&self.len_inverse);
+        }
+    }
+
+    /// Iterative radix-2 transform, not scaled.
+    fn transform(&self, values: &mut [T], roots: &[T]) {
+        let len = values.len();
+        assert!(
+            len == 1 << self.order,
+            "NTT of length {} with a plan for length {}",
+            len,
+            1usize << self.order
+        );
+        if len == 1 {
+            return;
+        }
+        for i in 0..len {
+            let j = i.reverse_bits() >> (usize::BITS - self.order);
+            if i < j {
+                values.swap(i, j);
+            }
+        }
+        let mut half = 1;
+        while half < len {
+            let step = len / (2 * half);
+            for block in values.chunks_mut(2 * half) {
+                let (lo, hi) = block.split_at_mut(half);
+                for (j, (x, y)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                    let t = y.mul_ref(&roots[j * step]);
+                    *y = x.sub_ref(&t);
+                    *x += t;
+                }
+            }
+            half *= 2;
+        }
+    }
+}
+
+/// A root of unity `w` of order `2^order` such that `w^(2^(order-1)) = -1`, among
+/// `candidates`.
+///
+/// Such a root makes the transform invertible even if the modulus is not prime. Candidates are
+/// typically `g^((n-1) / 2^order)` for small `g`; for a prime modulus `n` half of all `g` work.
+pub(crate) fn find_root<T: NttElement>(
+    order: u32,
+    minus_one: &T,
+    candidates: impl IntoIterator<Item = T>,
+) -> Option<T> {
+    debug_assert!(order >= 1);
+    candidates.into_iter().find(|root| {
+        let mut x = root.clone();
+        for _ in 1..order {
+            x = x.mul_ref(&x);
+        }
+        x == *minus_one
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
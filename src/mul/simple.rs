@@ -1,12 +1,15 @@
 //! Simple multiplication algorithm.
 
 use crate::{
+    add,
     arch::{
         self,
         word::{SignedWord, Word},
     },
     memory::{self, Memory},
     mul::{self, helpers},
+    primitive::{extend_word, split_double_word},
+    shift,
     sign::Sign::{self, *},
 };
 use alloc::alloc::Layout;
@@ -17,6 +20,9 @@ const CHUNK_LEN: usize = 1024;
 /// Max supported smaller factor length.
 pub(crate) const MAX_SMALLER_LEN: usize = CHUNK_LEN;
 
+/// Max supported length for squaring.
+pub(crate) const MAX_LEN_SQR: usize = 64;
+
 /// Temporary memory required for multiplication.
 ///
 /// n bounds the length of the smaller factor in words.
@@ -122,3 +128,44 @@ fn sub_mul_chunk(c: &mut [Word], a: &[Word], b: &[Word]) -> bool {
     }
     borrow
 }
+
+/// c += sign * a^2
+/// Simple method: O(a.len()^2 / 2).
+///
+/// Every product `a[i] * a[j]` with `i < j` is computed once and doubled, then the squares
+/// `a[i]^2` are added.
+///
+/// Returns carry.
+pub(crate) fn add_signed_sqr(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    _memory: &mut Memory,
+) -> SignedWord {
+    let n = a.len();
+    debug_assert!(c.len() == 2 * n);
+    debug_assert!(n <= MAX_LEN_SQR);
+
+    let mut buffer = [0; 2 * MAX_LEN_SQR];
+    let square = &mut buffer[..2 * n];
+    // Off-diagonal products.
+    // Row i covers [2i+1, i+n], and i+n is not touched by previous rows.
+    for i in 0..n.saturating_sub(1) {
+        square[i + n] =
+            mul::add_mul_word_same_len_in_place(&mut square[2 * i + 1..i + n], a[i], &a[i + 1..]);
+    }
+    let overflow = shift::shl_in_place(square, 1);
+    debug_assert!(overflow == 0);
+    // Diagonal squares.
+    let mut carry = false;
+    for (i, x) in a.iter().enumerate() {
+        let (lo, hi) = split_double_word(extend_word(*x) * extend_word(*x));
+        let (sum, carry_lo) = arch::add::add_with_carry(square[2 * i], lo, carry);
+        square[2 * i] = sum;
+        let (sum, carry_hi) = arch::add::add_with_carry(square[2 * i + 1], hi, carry_lo);
+        square[2 * i + 1] = sum;
+        carry = carry_hi;
+    }
+    debug_assert!(!carry);
+    add::add_signed_same_len_in_place(c, sign, square)
+}
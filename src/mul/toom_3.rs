@@ -263,3 +263,140 @@ pub(crate) fn add_signed_mul_same_len(
     assert!(carry.abs() <= 1);
     carry
 }
+
+/// c += sign * a^2
+/// Toom-Cook-3 method: O(n^1.47).
+///
+/// Same as `add_signed_mul_same_len` with `b = a`: only one operand is evaluated and V(-1) is
+/// never negative.
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_sqr(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    let n = a.len();
+    debug_assert!(c.len() == 2 * n);
+    debug_assert!(n >= MIN_LEN);
+
+    let n3 = (n + 2) / 3;
+    let n3_short = n - 2 * n3;
+
+    let (a0, a12) = a.split_at(n3);
+    let (a1, a2) = a12.split_at(n3);
+
+    let mut carry: SignedWord = 0;
+    let mut carry_c0: SignedWord = 0; // at 2*n3
+    let mut carry_c1: SignedWord = 0; // at 3*n3+2
+    let mut carry_c2: SignedWord = 0; // at 4*n3+2
+    let mut carry_c3: SignedWord = 0; // at 5*n3+2
+
+    // Evaluate at 0.
+    // V(0) = a0^2
+    // c_0 += V(0)
+    // c_2 -= V(0)
+    // t1 = 3*V(0)
+    let (t1, mut memory) = memory.allocate_slice_fill(2 * n3 + 2, 0);
+    {
+        let t1_short = &mut t1[..2 * n3];
+        let overflow = mul::add_signed_sqr(t1_short, Positive, a0, &mut memory);
+        assert!(overflow == 0);
+        carry_c0 += add::add_signed_same_len_in_place(&mut c[..2 * n3], sign, t1_short);
+        carry_c2 += add::add_signed_in_place(&mut c[2 * n3..4 * n3 + 2], -sign, t1_short);
+        t1[2 * n3] = mul::mul_word_in_place(t1_short, 3);
+        t1[2 * n3 + 1] = 0;
+    }
+
+    // Evaluate at 2.
+    // a_eval = a0 + 2a1 + 4a2
+    // V(2) = a_eval^2
+    // t1 += V(2)
+    let (a_eval, mut memory) = memory.allocate_slice_copy_fill(n3 + 1, a0, 0);
+    {
+        a_eval[n3] = mul::add_mul_word_same_len_in_place(&mut a_eval[..n3], 2, a1);
+        a_eval[n3] += mul::add_mul_word_in_place(&mut a_eval[..n3], 4, a2);
+        let overflow = mul::add_signed_sqr(t1, Positive, a_eval, &mut memory);
+        assert!(overflow == 0);
+    }
+
+    // Evaluate at inf.
+    // V(inf) = a4^2
+    // c_2 -= V(inf)
+    // c_4 += V(inf)
+    // t1 -= 12V(inf)
+    // Now t1 = 3V(0) + V(2) - 12V(inf)
+    {
+        let (c_eval, mut memory) = memory.allocate_slice_fill(2 * n3 + 2, 0);
+        let c_eval_short = &mut c_eval[..2 * n3_short];
+        let overflow = mul::add_signed_sqr(c_eval_short, Positive, a2, &mut memory);
+        assert!(overflow == 0);
+        carry_c2 += add::add_signed_in_place(&mut c[2 * n3..4 * n3 + 2], -sign, c_eval_short);
+        carry += add::add_signed_same_len_in_place(&mut c[4 * n3..], sign, c_eval_short);
+        c_eval[2 * n3_short] = mul::mul_word_in_place(c_eval_short, 12);
+        let overflow = add::sub_in_place(t1, &c_eval[..2 * n3_short + 1]);
+        // 3V(0) + V(2) - 12V(inf) is never negative
+        assert!(!overflow);
+    }
+
+    let (t2, mut memory) = memory.allocate_slice_fill(2 * n3 + 2, 0);
+    {
+        // Evaluate at 1.
+        // a_eval = a0 + a1 + a2
+        // V(1) = a_eval^2
+        // c_1 += V(1)
+        // t2 = V(1)
+        let (a02, mut memory) = memory.allocate_slice_copy_fill(n3 + 1, a0, 0);
+        a02[n3] = Word::from(add::add_in_place(&mut a02[..n3], a2));
+        a_eval.copy_from_slice(a02);
+        a_eval[n3] += Word::from(add::add_same_len_in_place(&mut a_eval[..n3], a1));
+
+        let overflow = mul::add_signed_sqr(t2, Positive, a_eval, &mut memory);
+        assert!(overflow == 0);
+        carry_c1 += add::add_signed_in_place(&mut c[n3..3 * n3 + 2], sign, t2);
+
+        // Evaluate at -1.
+        // a_eval = |a02 - a1|
+        // V(-1) = a_eval^2
+        a_eval.copy_from_slice(a02);
+        let _ = add::sub_in_place_with_sign(a_eval, a1);
+    }
+    // t2 += V(-1)
+    // t1 += 2*V(-1)
+    // Now t1 = 3V(0) + 2V(-1) + V(2) - 12V(inf),
+    //     t2 = V(1) + V(-1).
+    let (c_eval, mut memory) = memory.allocate_slice_fill(2 * (n3 + 1), 0);
+    let overflow = mul::add_signed_sqr(c_eval, Positive, a_eval, &mut memory);
+    assert!(overflow == 0);
+    let overflow = add::add_same_len_in_place(t2, c_eval);
+    assert!(!overflow);
+    let overflow = mul::add_mul_word_same_len_in_place(t1, 2, c_eval);
+    assert!(overflow == 0);
+
+    // t1 /= 6
+    // t2 /= 2
+    let t1_rem = div::div_by_word_in_place(t1, 6);
+    let t2_rem = shift::shr_in_place(t2, 1);
+    assert_eq!(t1_rem, 0);
+    assert_eq!(t2_rem, 0);
+
+    // c1 -= t1
+    // c3 += t1
+    // c2 += t2
+    // c3 -= t2
+    carry_c1 += add::add_signed_same_len_in_place(&mut c[n3..3 * n3 + 2], -sign, t1);
+    carry_c3 += add::add_signed_same_len_in_place(&mut c[3 * n3..5 * n3 + 2], sign, t1);
+    carry_c2 += add::add_signed_same_len_in_place(&mut c[2 * n3..4 * n3 + 2], sign, t2);
+    carry_c3 += add::add_signed_same_len_in_place(&mut c[3 * n3..5 * n3 + 2], -sign, t2);
+
+    // Apply carries.
+    carry_c1 += add::add_signed_word_in_place(&mut c[2 * n3..3 * n3 + 2], carry_c0);
+    carry_c2 += add::add_signed_word_in_place(&mut c[3 * n3 + 2..4 * n3 + 2], carry_c1);
+    carry_c3 += add::add_signed_word_in_place(&mut c[4 * n3 + 2..5 * n3 + 2], carry_c2);
+    carry += add::add_signed_word_in_place(&mut c[5 * n3 + 2..], carry_c3);
+
+    assert!(carry.abs() <= 1);
+    carry
+}
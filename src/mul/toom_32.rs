@@ -0,0 +1,112 @@
+//! Toom-Cook-3x2 multiplication algorithm for factors of unequal length.
+
+use crate::{
+    add,
+    arch::word::{SignedWord, Word},
+    memory::Memory,
+    mul::{self, helpers},
+    sign::Sign::{self, *},
+};
+
+/// Minimum supported length of the smaller factor.
+pub(crate) const MIN_LEN: usize = 16;
+
+// Temporary memory required is bounded by toom_42::memory_requirement_up_to.
+// In each level of recursion we use:
+// 4 values:        4 * (2k + 2)
+// a02, a_eval:     2 * (k + 1)
+// b_eval:          k + 1
+// total: 11k + 11
+// which is less than what Toom-4x2 uses.
+
+/// c += sign * a * b
+/// Toom-Cook-3x2 method, for 5/4 b.len() <= a.len() <= 7/4 b.len().
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    let n = b.len();
+    debug_assert!(c.len() == a.len() + n && n >= MIN_LEN);
+    debug_assert!(4 * a.len() >= 5 * n && 4 * a.len() <= 7 * n);
+
+    // We evaluate the polynomials A(x) = a0 + a1*x + a2*x^2, B(x) = b0 + b1*x
+    // at points 0, 1, -1, infinity.
+    // Multiplying, this gives us values of V(x) = A(x)*B(x) = c0 + c1*x + c2*x^2 + c3*x^3
+    // at the same points (using 4 recursive multiplications).
+    //
+    // Then we interpolate the polynomial coefficients:
+    // c0 = V(0)
+    // c1 = (V(1) - V(-1))/2 - c3
+    // c2 = V(1) - (V(1) - V(-1))/2 - c0
+    // c3 = V(inf)
+    //
+    // a2 and b1 are not empty and no longer than k.
+    let k = ((a.len() + 2) / 3).max((n + 1) / 2);
+    let len_value = 2 * k + 2;
+
+    let (values, mut memory) = memory.allocate_slice_fill::<Word>(4 * len_value, 0);
+    let (v0, values) = values.split_at_mut(len_value);
+    let (v1, values) = values.split_at_mut(len_value);
+    let (vm1, vinf) = values.split_at_mut(len_value);
+
+    let (a02, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+    let (a_eval, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+    let (b_eval, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+
+    let (a0, a12) = a.split_at(k);
+    let (a1, a2) = a12.split_at(k);
+    let (b0, b1) = b.split_at(k);
+
+    // V(0) = a0 * b0
+    helpers::signed_mul_same_len(v0, Positive, a0, b0, &mut memory);
+    // V(inf) = a2 * b1
+    let overflow = mul::add_signed_mul(
+        &mut vinf[..a2.len() + b1.len()],
+        Positive,
+        a2,
+        b1,
+        &mut memory,
+    );
+    assert!(overflow == 0);
+
+    // V(1) = (a0 + a1 + a2) * (b0 + b1)
+    a02[..k].copy_from_slice(a0);
+    a02[k] = Word::from(add::add_in_place(&mut a02[..k], a2));
+    a_eval.copy_from_slice(a02);
+    a_eval[k] += Word::from(add::add_same_len_in_place(&mut a_eval[..k], a1));
+    b_eval[..k].copy_from_slice(b0);
+    b_eval[k] = Word::from(add::add_in_place(&mut b_eval[..k], b1));
+    helpers::signed_mul_same_len(v1, Positive, a_eval, b_eval, &mut memory);
+
+    // V(-1) = (a0 - a1 + a2) * (b0 - b1)
+    a_eval.copy_from_slice(a02);
+    let mut value_neg1_sign = add::sub_in_place_with_sign(a_eval, a1);
+    b_eval[..k].copy_from_slice(b0);
+    b_eval[k] = 0;
+    value_neg1_sign *= add::sub_in_place_with_sign(b_eval, b1);
+    helpers::signed_mul_same_len(vm1, value_neg1_sign, a_eval, b_eval, &mut memory);
+
+    // Interpolate.
+    // vm1 = c1 + c3, v1 = c0 + c2
+    let _ = add::sub_same_len_in_place_swap(v1, vm1);
+    helpers::div_exact_signed_by_pow2_in_place(vm1, 1);
+    let _ = add::sub_same_len_in_place(v1, vm1);
+    // vm1 = c1, v1 = c2
+    let _ = add::sub_same_len_in_place(vm1, vinf);
+    let _ = add::sub_same_len_in_place(v1, v0);
+
+    // All coefficients are non-negative now.
+    let mut carry: SignedWord = 0;
+    for (i, coeff) in [&*v0, &*vm1, &*v1, &*vinf].iter().enumerate() {
+        carry += helpers::add_signed_coefficient(c, i * k, sign, coeff);
+    }
+
+    assert!(carry.abs() <= 1);
+    carry
+}
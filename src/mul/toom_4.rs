@@ -0,0 +1,440 @@
+//! Toom-Cook-4 multiplication algorithm.
+
+use crate::{
+    add,
+    arch::word::{SignedWord, Word},
+    math,
+    memory::{self, Memory},
+    mul::{self, helpers},
+    sign::Sign::{self, *},
+};
+use alloc::alloc::Layout;
+#[cfg(feature = "parallel")]
+use {
+    crate::{memory::MemoryAllocation, parallel},
+    alloc::{vec, vec::Vec},
+};
+
+// We must have 3 * k < n where k = ceil(n/4), so that the top part is not empty:
+// 3 * ceil(n/4) <= 3 * (n+3)/4 < n if n >= 10.
+//
+// The memory bound below requires n >= 100.
+/// Minimum supported length of the factors.
+pub(crate) const MIN_LEN: usize = 100;
+
+/// Temporary memory required for multiplication.
+///
+/// n bounds the length of the smaller factor in words.
+pub(crate) fn memory_requirement_up_to(n: usize) -> Layout {
+    // In each level of recursion we use:
+    // 7 values:                  7 * (2k + 2)
+    // a_even, a_odd, a_eval:     3 * (k + 1)
+    // b_even, b_odd, b_eval:     3 * (k + 1)
+    // total: 20k + 20 <= 5n + 35
+    //
+    // Prove by induction that f(n) <= 7n + 40 log_2 n for n >= 100.
+    // f(n)  = 5n + 35 + f(k+1)
+    //      <= 5n + 35 + 7(n+7)/4 + 40 log_2 ((n+7)/4)
+    //      <= 6.75n + 47.25 + 40 log_2 n - 40 * 1.9
+    //      <= 7n + 40 log_2 n
+    //
+    // Note: the recurrence also works when we transition to Toom-3, because
+    // Toom-3 memory requirements are smaller.
+    let num_words = 7 * n + 40 * (math::ceil_log_2(n) as usize);
+    memory::array_layout::<Word>(num_words)
+}
+
+/// c += sign * a * b
+/// Toom-Cook-4 method. O(a.len() * b.len()^0.40).
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    assert!(a.len() >= b.len() && b.len() >= MIN_LEN && c.len() == a.len() + b.len());
+
+    helpers::add_signed_mul_split_into_chunks(
+        c,
+        sign,
+        a,
+        b,
+        b.len(),
+        memory,
+        add_signed_mul_same_len,
+    )
+}
+
+/// c += sign * a * b
+/// Toom-Cook-4 method: O(n^1.40).
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul_same_len(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    debug_assert!(b.len() == a.len());
+    add_signed_mul_or_sqr_same_len(c, sign, a, b, memory)
+}
+
+/// c += sign * a^2
+/// Toom-Cook-4 method: O(n^1.40).
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_sqr(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    add_signed_mul_or_sqr_same_len(c, sign, a, a, memory)
+}
+
+/// c += sign * a * b
+/// Toom-Cook-4 method: O(n^1.40).
+///
+/// If a and b are the same slice, only a is evaluated and the products are squares.
+///
+/// Returns carry.
+fn add_signed_mul_or_sqr_same_len(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    let n = a.len();
+    debug_assert!(b.len() == n && c.len() == 2 * n);
+    debug_assert!(n >= MIN_LEN);
+
+    // Bodrato, Zanoni, Integer and polynomial multiplication: towards optimal Toom-Cook matrices.
+    //
+    // We evaluate the polynomials A(x) = a0 + a1*x + a2*x^2 + a3*x^3 and B(x) at points
+    // 0, 1, -1, 2, -2, 1/2, infinity.
+    // Multiplying, this gives us values of V(x) = A(x)*B(x) = c0 + c1*x + ... + c6*x^6
+    // at the same points (using 7 recursive multiplications). For the point 1/2 we use
+    // V'(1/2) = 64 V(1/2) which is an integer.
+    //
+    // Then we interpolate the polynomial coefficients in fixed-length two's complement
+    // arithmetic, with all divisions exact:
+    // o1 = (V(1) - V(-1))/2 = c1 + c3 + c5
+    // e1 = V(1) - o1 = c0 + c2 + c4 + c6
+    // o2 = (V(2) - V(-2))/4 = c1 + 4c3 + 16c5
+    // e2 = V(2) - 2o2 = c0 + 4c2 + 16c4 + 64c6
+    // c0 = V(0)
+    // c6 = V(inf)
+    // c4 = ((e2 - c0 - 64c6)/4 - (e1 - c0 - c6)) / 3
+    // c2 = e1 - c0 - c6 - c4
+    // h = (V'(1/2) - 64c0 - 16c2 - 4c4 - c6)/2 = 16c1 + 4c3 + c5
+    // p = (o2 - o1)/3 = c3 + 5c5
+    // q = (16o1 - h)/3 = 4c3 + 5c5
+    // c3 = (q - p)/3
+    // c5 = (p - c3)/5
+    // c1 = o1 - c3 - c5
+    let square = mul::is_same_slice(a, b);
+    let k = (n + 3) / 4;
+    let len_value = 2 * k + 2;
+
+    let (values, mut memory) = memory.allocate_slice_fill::<Word>(7 * len_value, 0);
+
+    #[cfg(feature = "parallel")]
+    {
+        if n >= parallel::MIN_LEN {
+            mul_parallel(a, b, k, square, values);
+            return interpolate(c, sign, k, values);
+        }
+    }
+
+    let (v0, rest) = values.split_at_mut(len_value);
+    let (v1, rest) = rest.split_at_mut(len_value);
+    let (vm1, rest) = rest.split_at_mut(len_value);
+    let (v2, rest) = rest.split_at_mut(len_value);
+    let (vm2, rest) = rest.split_at_mut(len_value);
+    let (vh, vinf) = rest.split_at_mut(len_value);
+
+    let (a_even, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+    let (a_odd, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+    let (a_eval, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+    let (b_even, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+    let (b_odd, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+    let (b_eval, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+
+    let (a0, _, _, a3) = split_4(a, k);
+    let (b0, _, _, b3) = split_4(b, k);
+
+    // V(0) = a0 * b0
+    helpers::signed_mul_same_len(v0, Positive, a0, b0, &mut memory);
+    // V(inf) = a3 * b3
+    helpers::signed_mul_same_len(vinf, Positive, a3, b3, &mut memory);
+
+    // V(1), V(-1)
+    even_odd_1(a, k, a_even, a_odd);
+    if !square {
+        even_odd_1(b, k, b_even, b_odd);
+    }
+    eval_plus(a_even, a_odd, a_eval);
+    if !square {
+        eval_plus(b_even, b_odd, b_eval);
+    }
+    mul_evaluated(v1, Positive, a_eval, b_eval, square, &mut memory);
+    let mut neg_sign = eval_minus(a_even, a_odd, a_eval);
+    if square {
+        neg_sign = Positive;
+    } else {
+        neg_sign *= eval_minus(b_even, b_odd, b_eval);
+    }
+    mul_evaluated(vm1, neg_sign, a_eval, b_eval, square, &mut memory);
+
+    // V(2), V(-2)
+    even_odd_2(a, k, a_even, a_odd);
+    if !square {
+        even_odd_2(b, k, b_even, b_odd);
+    }
+    eval_plus(a_even, a_odd, a_eval);
+    if !square {
+        eval_plus(b_even, b_odd, b_eval);
+    }
+    mul_evaluated(v2, Positive, a_eval, b_eval, square, &mut memory);
+    let mut neg_sign = eval_minus(a_even, a_odd, a_eval);
+    if square {
+        neg_sign = Positive;
+    } else {
+        neg_sign *= eval_minus(b_even, b_odd, b_eval);
+    }
+    mul_evaluated(vm2, neg_sign, a_eval, b_eval, square, &mut memory);
+
+    // V'(1/2)
+    eval_half(a, k, a_eval);
+    if !square {
+        eval_half(b, k, b_eval);
+    }
+    mul_evaluated(vh, Positive, a_eval, b_eval, square, &mut memory);
+
+    interpolate(c, sign, k, values)
+}
+
+/// Interpolate the values at 0, 1, -1, 2, -2, 1/2 (scaled by 64), infinity, each of length
+/// 2k + 2, and add sign * the polynomial at B^k to c.
+///
+/// Returns carry.
+fn interpolate(c: &mut [Word], sign: Sign, k: usize, values: &mut [Word]) -> SignedWord {
+    let len_value = 2 * k + 2;
+    debug_assert!(values.len() == 7 * len_value);
+    let (v0, values) = values.split_at_mut(len_value);
+    let (v1, values) = values.split_at_mut(len_value);
+    let (vm1, values) = values.split_at_mut(len_value);
+    let (v2, values) = values.split_at_mut(len_value);
+    let (vm2, values) = values.split_at_mut(len_value);
+    let (vh, vinf) = values.split_at_mut(len_value);
+
+    // Interpolate.
+    // vm1 = o1, v1 = e1
+    let _ = add::sub_same_len_in_place_swap(v1, vm1);
+    helpers::div_exact_signed_by_pow2_in_place(vm1, 1);
+    let _ = add::sub_same_len_in_place(v1, vm1);
+    // vm2 = o2, v2 = e2
+    let _ = add::sub_same_len_in_place_swap(v2, vm2);
+    helpers::div_exact_signed_by_pow2_in_place(vm2, 2);
+    let _ = mul::sub_mul_word_same_len_in_place(v2, 2, vm2);
+    // v1 = c2 + c4
+    let _ = add::sub_same_len_in_place(v1, v0);
+    let _ = add::sub_same_len_in_place(v1, vinf);
+    // v2 = c2 + 4c4
+    let _ = add::sub_same_len_in_place(v2, v0);
+    let _ = mul::sub_mul_word_same_len_in_place(v2, 64, vinf);
+    helpers::div_exact_signed_by_pow2_in_place(v2, 2);
+    // v2 = c4, v1 = c2
+    let _ = add::sub_same_len_in_place(v2, v1);
+    helpers::div_exact_signed_by_word_in_place(v2, 3);
+    let _ = add::sub_same_len_in_place(v1, v2);
+    // vh = h
+    let _ = mul::sub_mul_word_same_len_in_place(vh, 64, v0);
+    let _ = mul::sub_mul_word_same_len_in_place(vh, 16, v1);
+    let _ = mul::sub_mul_word_same_len_in_place(vh, 4, v2);
+    let _ = add::sub_same_len_in_place(vh, vinf);
+    helpers::div_exact_signed_by_pow2_in_place(vh, 1);
+    // vm2 = p
+    let _ = add::sub_same_len_in_place(vm2, vm1);
+    helpers::div_exact_signed_by_word_in_place(vm2, 3);
+    // vh = q
+    helpers::negate_in_place(vh);
+    let _ = mul::add_mul_word_same_len_in_place(vh, 16, vm1);
+    helpers::div_exact_signed_by_word_in_place(vh, 3);
+    // vh = c3, vm2 = c5, vm1 = c1
+    let _ = add::sub_same_len_in_place(vh, vm2);
+    helpers::div_exact_signed_by_word_in_place(vh, 3);
+    let _ = add::sub_same_len_in_place(vm2, vh);
+    helpers::div_exact_signed_by_word_in_place(vm2, 5);
+    let _ = add::sub_same_len_in_place(vm1, vh);
+    let _ = add::sub_same_len_in_place(vm1, vm2);
+
+    // All coefficients are non-negative now.
+    let mut carry: SignedWord = 0;
+    for (i, coeff) in [&*v0, &*vm1, &*v1, &*vh, &*v2, &*vm2, &*vinf]
+        .iter()
+        .enumerate()
+    {
+        carry += helpers::add_signed_coefficient(c, i * k, sign, coeff);
+    }
+
+    assert!(carry.abs() <= 1);
+    carry
+}
+
+/// Compute the 7 values of the product at the evaluation points into `values`, with the
+/// recursive multiplications running in parallel.
+///
+/// If a and b are the same slice, the products are squares.
+#[cfg(feature = "parallel")]
+fn mul_parallel(a: &[Word], b: &[Word], k: usize, square: bool, values: &mut [Word]) {
+    let (a_evals, a_sign_neg1, a_sign_neg2) = evaluate_all(a, k);
+    let b_evaluated = if square {
+        None
+    } else {
+        Some(evaluate_all(b, k))
+    };
+    let (b_evals, sign_neg1, sign_neg2) = match &b_evaluated {
+        None => (&a_evals, Positive, Positive),
+        Some((b_evals, b_sign_neg1, b_sign_neg2)) => (
+            b_evals,
+            a_sign_neg1 * *b_sign_neg1,
+            a_sign_neg2 * *b_sign_neg2,
+        ),
+    };
+
+    let (a0, _, _, a3) = split_4(a, k);
+    let (b0, _, _, b3) = split_4(b, k);
+    let a_eval = |i: usize| &a_evals[i * (k + 1)..(i + 1) * (k + 1)];
+    let b_eval = |i: usize| &b_evals[i * (k + 1)..(i + 1) * (k + 1)];
+    let factors = [
+        (Positive, a0, b0),
+        (Positive, a_eval(0), b_eval(0)),
+        (sign_neg1, a_eval(1), b_eval(1)),
+        (Positive, a_eval(2), b_eval(2)),
+        (sign_neg2, a_eval(3), b_eval(3)),
+        (Positive, a_eval(4), b_eval(4)),
+        (Positive, a3, b3),
+    ];
+
+    rayon::scope(|scope| {
+        for (value, &(sign, x, y)) in values.chunks_mut(2 * k + 2).zip(factors.iter()) {
+            scope.spawn(move |_| {
+                // Each task needs its own scratch space.
+                let mut allocation = MemoryAllocation::new(mul::memory_requirement_exact(
+                    x.len() + y.len(),
+                    x.len().min(y.len()),
+                ));
+                helpers::signed_mul_same_len(value, sign, x, y, &mut allocation.memory());
+            });
+        }
+    });
+}
+
+/// Evaluate X(1), |X(-1)|, X(2), |X(-2)| and X'(1/2), each k + 1 words long.
+///
+/// Returns the evaluations and the signs of X(-1) and X(-2).
+#[cfg(feature = "parallel")]
+fn evaluate_all(x: &[Word], k: usize) -> (Vec<Word>, Sign, Sign) {
+    let mut evals = vec![0; 5 * (k + 1)];
+    let mut even = vec![0; k + 1];
+    let mut odd = vec![0; k + 1];
+    let (eval_1, rest) = evals.split_at_mut(k + 1);
+    let (eval_neg1, rest) = rest.split_at_mut(k + 1);
+    let (eval_2, rest) = rest.split_at_mut(k + 1);
+    let (eval_neg2, eval_h) = rest.split_at_mut(k + 1);
+
+    even_odd_1(x, k, &mut even, &mut odd);
+    eval_plus(&even, &odd, eval_1);
+    let sign_neg1 = eval_minus(&even, &odd, eval_neg1);
+    even_odd_2(x, k, &mut even, &mut odd);
+    eval_plus(&even, &odd, eval_2);
+    let sign_neg2 = eval_minus(&even, &odd, eval_neg2);
+    eval_half(x, k, eval_h);
+
+    (evals, sign_neg1, sign_neg2)
+}
+
+/// Split into 3 parts of length k and the remaining part.
+pub(crate) fn split_4(x: &[Word], k: usize) -> (&[Word], &[Word], &[Word], &[Word]) {
+    let (x0, x123) = x.split_at(k);
+    let (x1, x23) = x123.split_at(k);
+    let (x2, x3) = x23.split_at(k);
+    (x0, x1, x2, x3)
+}
+
+/// even = x0 + x2
+/// odd = x1 + x3
+///
+/// Then X(1) = even + odd, X(-1) = even - odd.
+pub(crate) fn even_odd_1(x: &[Word], k: usize, even: &mut [Word], odd: &mut [Word]) {
+    let (x0, x1, x2, x3) = split_4(x, k);
+    even[..k].copy_from_slice(x0);
+    even[k] = Word::from(add::add_same_len_in_place(&mut even[..k], x2));
+    odd[..k].copy_from_slice(x1);
+    odd[k] = Word::from(add::add_in_place(&mut odd[..k], x3));
+}
+
+/// even = x0 + 4x2
+/// odd = 2x1 + 8x3
+///
+/// Then X(2) = even + odd, X(-2) = even - odd.
+pub(crate) fn even_odd_2(x: &[Word], k: usize, even: &mut [Word], odd: &mut [Word]) {
+    let (x0, x1, x2, x3) = split_4(x, k);
+    even[..k].copy_from_slice(x0);
+    even[k] = mul::add_mul_word_same_len_in_place(&mut even[..k], 4, x2);
+    odd[..k].copy_from_slice(x1);
+    odd[k] = mul::mul_word_in_place(&mut odd[..k], 2);
+    odd[k] += mul::add_mul_word_in_place(&mut odd[..k], 8, x3);
+}
+
+/// eval = 8x0 + 4x1 + 2x2 + x3
+fn eval_half(x: &[Word], k: usize, eval: &mut [Word]) {
+    let (x0, x1, x2, x3) = split_4(x, k);
+    eval[..x3.len()].copy_from_slice(x3);
+    for word in &mut eval[x3.len()..] {
+        *word = 0;
+    }
+    let mut carry = mul::add_mul_word_same_len_in_place(&mut eval[..k], 2, x2);
+    carry += mul::add_mul_word_same_len_in_place(&mut eval[..k], 4, x1);
+    carry += mul::add_mul_word_same_len_in_place(&mut eval[..k], 8, x0);
+    eval[k] = carry;
+}
+
+/// eval = even + odd
+pub(crate) fn eval_plus(even: &[Word], odd: &[Word], eval: &mut [Word]) {
+    eval.copy_from_slice(even);
+    let overflow = add::add_same_len_in_place(eval, odd);
+    debug_assert!(!overflow);
+}
+
+/// eval = |even - odd|
+///
+/// Returns the sign of even - odd.
+pub(crate) fn eval_minus(even: &[Word], odd: &[Word], eval: &mut [Word]) -> Sign {
+    eval.copy_from_slice(even);
+    add::sub_in_place_with_sign(eval, odd)
+}
+
+/// value = sign * a_eval * b_eval as a two's complement number.
+///
+/// If square, b_eval is ignored and a_eval is squared.
+fn mul_evaluated(
+    value: &mut [Word],
+    sign: Sign,
+    a_eval: &[Word],
+    b_eval: &[Word],
+    square: bool,
+    memory: &mut Memory,
+) {
+    let b_eval = if square { a_eval } else { b_eval };
+    helpers::signed_mul_same_len(value, sign, a_eval, b_eval, memory);
+}
@@ -0,0 +1,171 @@
+//! Toom-Cook-4x2 multiplication algorithm for factors of unequal length.
+
+use crate::{
+    add,
+    arch::word::{SignedWord, Word},
+    math,
+    memory::{self, Memory},
+    mul::{self, helpers, toom_4},
+    sign::Sign::{self, *},
+};
+use alloc::alloc::Layout;
+
+/// Minimum supported length of the smaller factor.
+pub(crate) const MIN_LEN: usize = 16;
+
+/// Temporary memory required for unbalanced multiplication using Toom-3x2 or Toom-4x2.
+///
+/// n bounds the length of the smaller factor in words.
+pub(crate) fn memory_requirement_up_to(n: usize) -> Layout {
+    // In each level of recursion we use:
+    // 5 values:                  5 * (2k + 2)
+    // a_even, a_odd, a_eval:     3 * (k + 1)
+    // b_eval:                    k + 1
+    // total: 14k + 14
+    // where k <= 5/8 n + 1.
+    //
+    // The recursive calls are either balanced, of length k + 1, or V(inf) which may be
+    // unbalanced again with the smaller length at most n/2.
+    //
+    // Prove by induction that f(n) <= 25n + 100 log_2 n.
+    // f(n) <= 8.75n + 28 + max(7(5/8 n + 2) + 40 log_2 n, 12.5n + 100 (log_2 n - 1))
+    //      <= 25n + 100 log_2 n
+    let num_words = 25 * n + 100 * (math::ceil_log_2(n) as usize);
+    memory::array_layout::<Word>(num_words)
+}
+
+/// c += sign * a * b
+/// Toom-Cook-4x2 method, for a.len() >= 7/4 b.len().
+///
+/// Returns carry.
+#[must_use]
+pub(crate) fn add_signed_mul(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    let n = b.len();
+    debug_assert!(c.len() == a.len() + n && n >= MIN_LEN);
+    debug_assert!(4 * a.len() >= 7 * n);
+
+    if 2 * a.len() <= 5 * n {
+        add_signed_mul_chunk(c, sign, a, b, memory)
+    } else {
+        helpers::add_signed_mul_split_into_chunks(
+            c,
+            sign,
+            a,
+            b,
+            2 * n,
+            memory,
+            add_signed_mul_chunk,
+        )
+    }
+}
+
+/// c += sign * a * b
+/// Toom-Cook-4x2 method, for 7/4 b.len() <= a.len() <= 5/2 b.len().
+///
+/// Returns carry.
+fn add_signed_mul_chunk(
+    c: &mut [Word],
+    sign: Sign,
+    a: &[Word],
+    b: &[Word],
+    memory: &mut Memory,
+) -> SignedWord {
+    let n = b.len();
+    debug_assert!(c.len() == a.len() + n);
+    debug_assert!(4 * a.len() >= 7 * n && 2 * a.len() <= 5 * n);
+
+    // We evaluate the polynomials A(x) = a0 + a1*x + a2*x^2 + a3*x^3, B(x) = b0 + b1*x
+    // at points 0, 1, -1, 2, infinity.
+    // Multiplying, this gives us values of V(x) = A(x)*B(x) = c0 + c1*x + ... + c4*x^4
+    // at the same points (using 5 recursive multiplications).
+    //
+    // Then we interpolate the polynomial coefficients:
+    // o1 = (V(1) - V(-1))/2 = c1 + c3
+    // c0 = V(0)
+    // c4 = V(inf)
+    // c2 = V(1) - o1 - c0 - c4
+    // c3 = ((V(2) - c0 - 4c2 - 16c4)/2 - o1) / 3
+    // c1 = o1 - c3
+    //
+    // a3 and b1 are not empty and no longer than k.
+    let k = ((a.len() + 3) / 4).max((n + 1) / 2);
+    let len_value = 2 * k + 2;
+
+    let (values, mut memory) = memory.allocate_slice_fill::<Word>(5 * len_value, 0);
+    let (v0, values) = values.split_at_mut(len_value);
+    let (v1, values) = values.split_at_mut(len_value);
+    let (vm1, values) = values.split_at_mut(len_value);
+    let (v2, vinf) = values.split_at_mut(len_value);
+
+    let (a_even, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+    let (a_odd, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+    let (a_eval, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+    let (b_eval, mut memory) = memory.allocate_slice_fill::<Word>(k + 1, 0);
+
+    let (a0, _, _, a3) = toom_4::split_4(a, k);
+    let (b0, b1) = b.split_at(k);
+
+    // V(0) = a0 * b0
+    helpers::signed_mul_same_len(v0, Positive, a0, b0, &mut memory);
+    // V(inf) = a3 * b1
+    let overflow = mul::add_signed_mul(
+        &mut vinf[..a3.len() + b1.len()],
+        Positive,
+        a3,
+        b1,
+        &mut memory,
+    );
+    assert!(overflow == 0);
+
+    // V(1) = A(1) * (b0 + b1)
+    toom_4::even_odd_1(a, k, a_even, a_odd);
+    toom_4::eval_plus(a_even, a_odd, a_eval);
+    b_eval[..k].copy_from_slice(b0);
+    b_eval[k] = Word::from(add::add_in_place(&mut b_eval[..k], b1));
+    helpers::signed_mul_same_len(v1, Positive, a_eval, b_eval, &mut memory);
+
+    // V(-1) = A(-1) * (b0 - b1)
+    let mut value_neg1_sign = toom_4::eval_minus(a_even, a_odd, a_eval);
+    b_eval[..k].copy_from_slice(b0);
+    b_eval[k] = 0;
+    value_neg1_sign *= add::sub_in_place_with_sign(b_eval, b1);
+    helpers::signed_mul_same_len(vm1, value_neg1_sign, a_eval, b_eval, &mut memory);
+
+    // V(2) = A(2) * (b0 + 2b1)
+    toom_4::even_odd_2(a, k, a_even, a_odd);
+    toom_4::eval_plus(a_even, a_odd, a_eval);
+    b_eval[..k].copy_from_slice(b0);
+    b_eval[k] = mul::add_mul_word_in_place(&mut b_eval[..k], 2, b1);
+    helpers::signed_mul_same_len(v2, Positive, a_eval, b_eval, &mut memory);
+
+    // Interpolate.
+    // vm1 = o1, v1 = c2
+    let _ = add::sub_same_len_in_place_swap(v1, vm1);
+    helpers::div_exact_signed_by_pow2_in_place(vm1, 1);
+    let _ = add::sub_same_len_in_place(v1, vm1);
+    let _ = add::sub_same_len_in_place(v1, v0);
+    let _ = add::sub_same_len_in_place(v1, vinf);
+    // v2 = c3, vm1 = c1
+    let _ = add::sub_same_len_in_place(v2, v0);
+    let _ = mul::sub_mul_word_same_len_in_place(v2, 4, v1);
+    let _ = mul::sub_mul_word_same_len_in_place(v2, 16, vinf);
+    helpers::div_exact_signed_by_pow2_in_place(v2, 1);
+    let _ = add::sub_same_len_in_place(v2, vm1);
+    helpers::div_exact_signed_by_word_in_place(v2, 3);
+    let _ = add::sub_same_len_in_place(vm1, v2);
+
+    // All coefficients are non-negative now.
+    let mut carry: SignedWord = 0;
+    for (i, coeff) in [&*v0, &*vm1, &*v1, &*v2, &*vinf].iter().enumerate() {
+        carry += helpers::add_signed_coefficient(c, i * k, sign, coeff);
+    }
+
+    assert!(carry.abs() <= 1);
+    carry
+}
@@ -299,6 +299,25 @@ impl_mul_ibig_primitive!(i128);
 impl_mul_ibig_primitive!(isize);
 
 impl UBig {
+    /// Square.
+    ///
+    /// Faster than multiplying two different numbers of the same length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(12).square(), ubig!(144));
+    /// ```
+    #[inline]
+    pub fn square(&self) -> UBig {
+        match self.repr() {
+            Small(word) => UBig::mul_word(*word, *word),
+            // mul_large detects that both operands are the same and squares.
+            Large(buffer) => UBig::mul_large(buffer, buffer),
+        }
+    }
+
     #[inline]
     pub fn mul_stack(stack: &mut dyn Stack, lhs: UBig, rhs: UBig) -> UBig {
         match (lhs.into_repr(), rhs.into_repr()) {
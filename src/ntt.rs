@@ -0,0 +1,208 @@
+//! Number-theoretic transform.
+//!
+//! Transforms of sequences of [ConstModulo] values modulo an NTT-friendly prime
+//! `M = k * 2^n + 1`, of lengths `2^order` for `order <= n`. [PRIMES] are word-sized primes with
+//! large `n`, but any suitable prime that fits in a word works.
+//!
+//! # Examples
+//!
+//! ```
+//! # use ibig::{modular::ConstModulo, ntt::{self, Ntt}};
+//! // 12289 = 3 * 2^12 + 1
+//! const P: u64 = 12289;
+//! assert_eq!(ntt::convolution::<P>(&[1, 2, 3], &[4, 5]), [4, 13, 22, 15]);
+//!
+//! let plan = Ntt::<P>::new(2).unwrap();
+//! let mut a = [1, 2, 3, 0].map(ConstModulo::<P>::new);
+//! let mut b = [4, 5, 0, 0].map(ConstModulo::<P>::new);
+//! plan.forward(&mut a);
+//! plan.forward(&mut b);
+//! ntt::pointwise_mul(&mut a, &b);
+//! plan.inverse(&mut a);
+//! assert_eq!(a, [4, 13, 22, 15].map(ConstModulo::<P>::new));
+//! ```
+
+use crate::{
+    arch::ntt::{MAX_ORDER, PRIMES as ARCH_PRIMES},
+    modular::ConstModulo,
+    mul::ntt::{self, Transform, NUM_PRIMES},
+    ubig::UBig,
+};
+use alloc::{vec, vec::Vec};
+
+/// Primes of the form `k * 2^n + 1` with large `n`, from the tables generated by the
+/// `ntt_primes` tool.
+///
+/// They use the whole machine word, so the values depend on the target.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::ntt::{Ntt, PRIMES};
+/// assert!(Ntt::<{ PRIMES[0] }>::max_order() >= 10);
+/// ```
+#[allow(clippy::unnecessary_cast)]
+pub const PRIMES: [u64; NUM_PRIMES] = [
+    ARCH_PRIMES[0].prime as u64,
+    ARCH_PRIMES[1].prime as u64,
+    ARCH_PRIMES[2].prime as u64,
+];
+
+/// Number of candidates to try when looking for a root of unity.
+const ROOT_CANDIDATES: u64 = 64;
+
+/// Precomputed roots of unity for transforms of length `2^order` modulo `M`.
+///
+/// The transform of `a` is `A[j] = sum a[i] w^(i j)` for a root of unity `w` of order `2^order`.
+/// Both the input and the output are in natural order.
+pub struct Ntt<const M: u64> {
+    transform: Transform<ConstModulo<M>>,
+}
+
+impl<const M: u64> Ntt<M> {
+    /// The largest order that `M` can support: the exponent of 2 in `M - 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ntt::Ntt;
+    /// assert_eq!(Ntt::<12289>::max_order(), 12);
+    /// assert_eq!(Ntt::<65519>::max_order(), 1);
+    /// ```
+    #[inline]
+    pub fn max_order() -> u32 {
+        if M < 3 {
+            0
+        } else {
+            (M - 1).trailing_zeros()
+        }
+    }
+
+    /// Prepare transforms of length `2^order`.
+    ///
+    /// Returns `None` if `order` is larger than [Ntt::max_order], or if no root of unity of
+    /// order `2^order` is found, which may happen if `M` is not prime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ibig::ntt::Ntt;
+    /// assert!(Ntt::<12289>::new(10).is_some());
+    /// assert!(Ntt::<12289>::new(13).is_none());
+    /// ```
+    pub fn new(order: u32) -> Option<Ntt<M>> {
+        if order > Ntt::<M>::max_order() {
+            return None;
+        }
+        let root = Ntt::<M>::find_root(order)?;
+        let len_inverse = ConstModulo::new(1 << order).inverse()?;
+        let transform = Transform::new(order, &root, ConstModulo::new(1), len_inverse);
+        Some(Ntt { transform })
+    }
+
+    /// The transform length is `2^order`.
+    #[inline]
+    pub fn order(&self) -> u32 {
+        self.transform.order()
+    }
+
+    /// Forward transform in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values.len()` is not `2^order`.
+    pub fn forward(&self, values: &mut [ConstModulo<M>]) {
+        self.transform.forward(values);
+    }
+
+    /// Inverse transform in place, including the division by the length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values.len()` is not `2^order`.
+    pub fn inverse(&self, values: &mut [ConstModulo<M>]) {
+        self.transform.inverse(values);
+    }
+
+    /// A root of unity `w` of order `2^order` such that `w^(2^(order-1)) = -1`.
+    ///
+    /// For [PRIMES] up to the library's own maximum order, this is a power of the precomputed
+    /// root. Otherwise candidates are `g^((M-1) / 2^order)` for small `g`.
+    #[allow(clippy::unnecessary_cast)]
+    fn find_root(order: u32) -> Option<ConstModulo<M>> {
+        if order == 0 {
+            return Some(ConstModulo::new(1));
+        }
+        if order <= MAX_ORDER {
+            if let Some(prime) = ARCH_PRIMES.iter().find(|p| p.prime as u64 == M) {
+                let mut root = ConstModulo::new(prime.max_order_root as u64);
+                for _ in order..MAX_ORDER {
+                    root *= root;
+                }
+                return Some(root);
+            }
+        }
+        let cofactor = UBig::from((M - 1) >> order);
+        let candidates = (2..ROOT_CANDIDATES + 2).map(|g| ConstModulo::<M>::new(g).pow(&cofactor));
+        ntt::find_root(order, &ConstModulo::new(M - 1), candidates)
+    }
+}
+
+/// Multiply element by element: `values[i] *= rhs[i]`.
+///
+/// # Panics
+///
+/// Panics if the lengths differ.
+pub fn pointwise_mul<const M: u64>(values: &mut [ConstModulo<M>], rhs: &[ConstModulo<M>]) {
+    assert_eq!(
+        values.len(),
+        rhs.len(),
+        "Pointwise product of different lengths"
+    );
+    for (x, y) in values.iter_mut().zip(rhs) {
+        *x *= *y;
+    }
+}
+
+/// Convolution modulo `M`: `c[k] = sum a[i] b[k - i] (mod M)`.
+///
+/// The result has `a.len() + b.len() - 1` elements, or none if either input is empty. The
+/// inputs don't have to be reduced modulo `M`.
+///
+/// # Examples
+///
+/// ```
+/// # use ibig::ntt;
+/// // (1 + 2x)^2 = 1 + 4x + 4x^2
+/// assert_eq!(ntt::convolution::<12289>(&[1, 2], &[1, 2]), [1, 4, 4]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `M` does not support a transform long enough for the result.
+pub fn convolution<const M: u64>(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let res_len = a.len() + b.len() - 1;
+    let order = res_len.next_power_of_two().trailing_zeros();
+    let plan = match Ntt::<M>::new(order) {
+        Some(plan) => plan,
+        None => panic!("Modulus {} does not support an NTT of order {}", M, order),
+    };
+
+    let to_padded = |x: &[u64]| {
+        let mut v = vec![ConstModulo::<M>::new(0); 1 << order];
+        for (v, &x) in v.iter_mut().zip(x) {
+            *v = ConstModulo::new(x);
+        }
+        v
+    };
+    let mut fa = to_padded(a);
+    let mut fb = to_padded(b);
+    plan.forward(&mut fa);
+    plan.forward(&mut fb);
+    pointwise_mul(&mut fa, &fb);
+    plan.inverse(&mut fa);
+    fa[..res_len].iter().map(|x| x.residue()).collect()
+}
@@ -0,0 +1,4 @@
+//! Parallel execution of independent parts of very large computations.
+
+/// Operands at least this many words long are worth splitting across threads.
+pub(crate) const MIN_LEN: usize = 2000;
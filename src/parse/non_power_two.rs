@@ -1,7 +1,9 @@
 //! Parse in a non-power-of-two radix.
 
+#[cfg(feature = "parallel")]
+use crate::parallel;
 use crate::{
-    arch::word::Word,
+    arch::{self, word::Word},
     buffer::Buffer,
     error::ParseError,
     mul,
@@ -11,7 +13,7 @@ use crate::{
 use alloc::vec;
 
 /// Parse in chunks of CHUNK_LEN * digits_per_word.
-const CHUNK_LEN: usize = 256;
+const CHUNK_LEN: usize = arch::thresholds::PARSE_CHUNK_LEN;
 
 /// Parse an unsigned string to [UBig].
 pub(crate) fn parse(src: &str, radix: Digit) -> Result<UBig, ParseError> {
@@ -106,6 +108,32 @@ fn parse_large_divide_conquer(
                 parse_large_divide_conquer(bytes, radix, chunk_bytes, radix_powers)
             } else {
                 let (bytes_hi, bytes_lo) = bytes.split_at(bytes.len() - bytes_lo_len);
+
+                #[cfg(feature = "parallel")]
+                {
+                    if radix_power.len() >= parallel::MIN_LEN {
+                        let (res_hi, res_lo) = rayon::join(
+                            || {
+                                parse_large_divide_conquer(
+                                    bytes_hi,
+                                    radix,
+                                    chunk_bytes,
+                                    radix_powers,
+                                )
+                            },
+                            || {
+                                parse_large_divide_conquer(
+                                    bytes_lo,
+                                    radix,
+                                    chunk_bytes,
+                                    radix_powers,
+                                )
+                            },
+                        );
+                        return Ok(res_hi? * radix_power + res_lo?);
+                    }
+                }
+
                 let res_hi =
                     parse_large_divide_conquer(bytes_hi, radix, chunk_bytes, radix_powers)?;
                 let res_lo =
@@ -21,7 +21,7 @@ impl UBig {
         match exp {
             0 => return UBig::from_word(1),
             1 => return self.clone(),
-            2 => return self * self,
+            2 => return self.square(),
             _ => {}
         }
         match self.repr() {
@@ -35,7 +35,7 @@ impl UBig {
             _ => {}
         }
         let mut p = usize::BIT_SIZE - 2 - exp.leading_zeros();
-        let mut res = self * self;
+        let mut res = self.square();
         loop {
             if exp & (1 << p) != 0 {
                 res *= self;
@@ -44,7 +44,7 @@ impl UBig {
                 break;
             }
             p -= 1;
-            res = &res * &res;
+            res = res.square();
         }
         res
     }
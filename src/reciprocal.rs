@@ -0,0 +1,93 @@
+//! Reciprocals by Newton's method.
+
+use crate::{
+    arch::{self, word::Word},
+    div_ops::panic_divide_by_0,
+    ibig::IBig,
+    primitive::WORD_BITS_USIZE,
+    ubig::{Repr::*, UBig},
+};
+use core::convert::TryFrom;
+
+/// Approximations keep this many bits more than the precision they are needed for.
+const GUARD_BITS: usize = 32;
+
+/// With fewer bits in the result, divide directly rather than use Newton's method.
+const MIN_BITS_NEWTON: usize = arch::thresholds::DIV_MIN_LEN_NEWTON * WORD_BITS_USIZE;
+
+impl UBig {
+    /// Reciprocal: `floor(2^precision / self)`.
+    ///
+    /// Computed by Newton's method. For `x <= 2^precision`, `(x * r) >> precision` where `r`
+    /// is the reciprocal is either `x / self` or one less, which allows repeated division by
+    /// the same large divisor using only multiplications.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ibig::ubig;
+    /// assert_eq!(ubig!(3).reciprocal(10), ubig!(341));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    pub fn reciprocal(&self, precision: usize) -> UBig {
+        if let Small(0) = self.repr() {
+            panic_divide_by_0();
+        }
+        if precision < self.bit_len() + MIN_BITS_NEWTON {
+            return (UBig::from_word(1) << precision) / self;
+        }
+
+        // Newton's method leaves an error of a few units, fix it exactly.
+        let mut y = reciprocal_approx(self, precision);
+        let one = UBig::from_word(1);
+        let power = &one << precision;
+        let mut dy = self * &y;
+        while dy > power {
+            y -= &one;
+            dy -= self;
+        }
+        let mut rem = power - dy;
+        while rem >= *self {
+            y += &one;
+            rem -= self;
+        }
+        y
+    }
+}
+
+/// Approximately `floor(2^precision / d)`, off by at most a few units.
+fn reciprocal_approx(d: &UBig, precision: usize) -> UBig {
+    let n = d.bit_len();
+    if precision < n + MIN_BITS_NEWTON {
+        return (UBig::from_word(1) << precision) / d;
+    }
+    // The result has h or h + 1 bits.
+    let h = precision - n;
+
+    // Only the top h + GUARD_BITS bits of d affect the result by less than 1.
+    if n > h + GUARD_BITS {
+        let t = n - h - GUARD_BITS;
+        return reciprocal_approx(&(d >> t), precision - t);
+    }
+
+    // y1 approximates 2^(n + h1) / d to about h1 bits, one Newton step doubles that.
+    // With s = h - h1 and e = 2^(n + h1) - d * y1:
+    // y = y1 * 2^s + y1 * 2^s * e * 2^s / 2^precision = y1 * 2^s + y1 * e / 2^(n + 2h1 - h)
+    // where |e| is a few times d. The low t bits of e change the correction by less than 1/2.
+    let h1 = h / 2 + GUARD_BITS;
+    let y1 = reciprocal_approx(d, n + h1);
+    let e = IBig::from(UBig::from_word(1) << (n + h1)) - IBig::from(d * &y1);
+    let t = (n + h1).saturating_sub(h + 2);
+    let correction = (IBig::from(&y1) * (e >> t)) >> (n + 2 * h1 - h - t);
+    UBig::try_from(IBig::from(y1 << (h - h1)) + correction).unwrap()
+}
+
+/// `floor(B^(2n) / rhs)` where `rhs` has n words and is normalized.
+///
+/// The result has n + 1 words, the top word is 1 or 2.
+pub(crate) fn reciprocal_normalized(rhs: &[Word]) -> UBig {
+    UBig::from_words(rhs).reciprocal(2 * rhs.len() * WORD_BITS_USIZE)
+}
@@ -203,6 +203,9 @@ impl UBig {
     /// Shift left one non-zero `Word` by `rhs` bits.
     fn shl_word_slow(word: Word, rhs: usize) -> UBig {
         let shift_words = rhs / WORD_BITS_USIZE;
+        if shift_words >= UBig::MAX_LEN {
+            UBig::panic_number_too_large();
+        }
         let shift_bits = (rhs % WORD_BITS_USIZE) as u32;
         let (lo, hi) = split_double_word(extend_word(word) << shift_bits);
         let mut buffer = Buffer::allocate(shift_words + 2);
@@ -231,6 +234,9 @@ impl UBig {
     fn shl_ref_large(words: &[Word], rhs: usize) -> UBig {
         let shift_words = rhs / WORD_BITS_USIZE;
         let shift_bits = (rhs % WORD_BITS_USIZE) as u32;
+        if shift_words > UBig::MAX_LEN - words.len() {
+            UBig::panic_number_too_large();
+        }
 
         let mut buffer = Buffer::allocate(shift_words + words.len() + 1);
         buffer.push_zeros(shift_words);
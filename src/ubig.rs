@@ -1,12 +1,7 @@
 //! Unsigned big integer.
 
 use self::Repr::*;
-use crate::{
-    arch::{ntt, word::Word},
-    buffer::Buffer,
-    math,
-    primitive::WORD_BITS_USIZE,
-};
+use crate::{arch::word::Word, buffer::Buffer, primitive::WORD_BITS_USIZE};
 use core::slice;
 
 /// Internal representation of UBig.
@@ -49,6 +44,13 @@ impl UBig {
         UBig(Small(word))
     }
 
+    /// Convert words into UBig.
+    pub(crate) fn from_words(words: &[Word]) -> UBig {
+        let mut buffer = Buffer::allocate(words.len());
+        buffer.extend(words);
+        buffer.into()
+    }
+
     /// Get the representation of UBig.
     #[inline]
     pub(crate) fn repr(&self) -> &Repr {
@@ -87,17 +89,11 @@ impl UBig {
     ///
     /// This also guarantees that up to 16 * length will not overflow.
     ///
-    /// We also make sure that any multiplication whose result fits in `MAX_LEN` can fit
-    /// within the largest possible number-theoretic transform.
+    /// Multiplication does not depend on the number-theoretic transform, so its maximum order
+    /// doesn't limit the length.
     ///
     /// Also make sure this is even, useful for checking whether a square will overflow.
-    pub(crate) const MAX_LEN: usize = math::min_usize(
-        usize::MAX / WORD_BITS_USIZE,
-        match 1usize.checked_shl(ntt::MAX_ORDER) {
-            Some(ntt_len) => ntt_len,
-            None => usize::MAX,
-        },
-    ) & !1usize;
+    pub(crate) const MAX_LEN: usize = (usize::MAX / WORD_BITS_USIZE) & !1usize;
 
     /// Maximum length in bits.
     ///
@@ -162,6 +158,7 @@ impl From<Buffer> for UBig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use static_assertions::const_assert;
 
     /// Current capacity in Words.
     fn capacity(x: &UBig) -> usize {
@@ -206,6 +203,13 @@ mod tests {
         assert_eq!(capacity(&num), 6);
     }
 
+    #[test]
+    fn test_max_len() {
+        // Only bit addressing limits the length.
+        const_assert!(UBig::MAX_BIT_LEN > usize::MAX - 2 * WORD_BITS_USIZE);
+        const_assert!(UBig::MAX_LEN % 2 == 0);
+    }
+
     #[test]
     fn test_clone() {
         let a = UBig::from_word(5);
@@ -1,7 +1,7 @@
 use ibig::{
     ibig,
     ops::{DivEuclid, DivRem, DivRemEuclid, RemEuclid},
-    ubig, IBig,
+    ubig, Divisor, IBig,
 };
 
 #[test]
@@ -141,6 +141,98 @@ fn test_div_rem_ubig() {
     }
 }
 
+#[test]
+fn test_div_rem_ubig_large() {
+    // Lengths in bits up to the Newton division tier, with quotients of different lengths.
+    let lens = [2000, 20000, 100000, 300000, 600000];
+    for &len_b in &lens {
+        for &len_q in &lens {
+            let b = (ubig!(3).pow(len_b * 10 / 16) >> 2) + ubig!(1);
+            let q = ubig!(7).pow(len_q * 10 / 28) - ubig!(1);
+            let r = &b - ubig!(1);
+            let a = &b * &q + &r;
+            assert_eq!((&a).div_rem(&b), (q.clone(), r.clone()));
+            assert_eq!((&a - &r - ubig!(1)) / &b, &q - ubig!(1));
+
+            // Extreme divisors: 2^k and 2^k - 1.
+            let b = ubig!(1) << len_b;
+            assert_eq!((&a).div_rem(&b), (&a >> len_b, &a & (&b - ubig!(1))));
+            let b = (ubig!(1) << len_b) - ubig!(1);
+            let (quot, rem) = (&a).div_rem(&b);
+            assert!(rem < b);
+            assert_eq!(quot * &b + rem, a);
+        }
+    }
+}
+
+#[test]
+fn test_divisor() {
+    let divisors = [
+        ubig!(1),
+        ubig!(3),
+        ubig!(0x8000000000000000),
+        ubig!(0xffffffffffffffff),
+        ubig!(_0x1234567890abcdef1234567890abcdef),
+        ubig!(3).pow(1000),
+        // Long enough to cache a reciprocal.
+        (ubig!(7).pow(40000) >> 1) + ubig!(1),
+        ubig!(1) << 100000,
+    ];
+    for b in &divisors {
+        let divisor = Divisor::new(b);
+        assert_eq!(divisor.divisor(), *b);
+        let dividends = [
+            ubig!(0),
+            ubig!(5),
+            b - ubig!(1),
+            b.clone(),
+            b * ubig!(17),
+            b.pow(2) - ubig!(1),
+            b.pow(3) + ubig!(1),
+            ubig!(3).pow(5 * b.bit_len() + 1000),
+        ];
+        for a in &dividends {
+            let qr = a.div_rem(b);
+            assert_eq!(divisor.div_rem(a), qr);
+            assert_eq!(divisor.div(a), qr.0);
+            assert_eq!(divisor.rem(a), qr.1);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_divisor_0() {
+    let _ = Divisor::new(&ubig!(0));
+}
+
+#[test]
+fn test_reciprocal() {
+    assert_eq!(ubig!(1).reciprocal(0), ubig!(1));
+    assert_eq!(ubig!(1).reciprocal(100), ubig!(1) << 100);
+    assert_eq!(ubig!(3).reciprocal(1), ubig!(0));
+    assert_eq!(ubig!(3).reciprocal(10), ubig!(341));
+    assert_eq!(ubig!(1000).reciprocal(5), ubig!(0));
+
+    for &len in &[10, 100, 1000, 10000, 100000, 400000] {
+        let a = (ubig!(3).pow(len * 10 / 16) >> 1) + ubig!(1);
+        for &precision in &[len / 2, len, 2 * len, 3 * len + 17, 5 * len] {
+            let r = a.reciprocal(precision);
+            assert_eq!(r, (ubig!(1) << precision) / &a);
+        }
+        let b = ubig!(1) << len;
+        assert_eq!(b.reciprocal(2 * len + 1), ubig!(1) << (len + 1));
+        let b = &b - ubig!(1);
+        assert_eq!(b.reciprocal(3 * len), (ubig!(1) << (3 * len)) / &b);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_reciprocal_0() {
+    let _ = ubig!(0).reciprocal(10);
+}
+
 #[test]
 #[should_panic]
 fn test_divide_by_0_ubig() {
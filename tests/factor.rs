@@ -0,0 +1,150 @@
+use ibig::{factor::FactorBudget, ubig, UBig};
+use std::convert::TryInto;
+
+fn product(factors: &[(UBig, usize)]) -> UBig {
+    factors
+        .iter()
+        .fold(ubig!(1), |acc, (p, mult)| acc * p.pow(*mult))
+}
+
+#[test]
+fn test_factor_small() {
+    assert_eq!(ubig!(1).factor(), []);
+    assert_eq!(ubig!(2).factor(), [(ubig!(2), 1)]);
+    assert_eq!(ubig!(1024).factor(), [(ubig!(2), 10)]);
+    assert_eq!(
+        ubig!(360).factor(),
+        [(ubig!(2), 3), (ubig!(3), 2), (ubig!(5), 1)]
+    );
+    assert_eq!(ubig!(997).factor(), [(ubig!(997), 1)]);
+    assert_eq!(ubig!(1018081).factor(), [(ubig!(1009), 2)]);
+
+    for n in 1u32..=2000 {
+        let factors = UBig::from(n).factor();
+        assert_eq!(product(&factors), UBig::from(n));
+        for (p, _) in &factors {
+            let p: u32 = p.try_into().unwrap();
+            assert!((2..p).take_while(|d| d * d <= p).all(|d| p % d != 0));
+        }
+    }
+}
+
+#[test]
+fn test_factor_large() {
+    let test_cases = [
+        (
+            ubig!(2).pow(64) + ubig!(1),
+            vec![(ubig!(274177), 1), (ubig!(67280421310721), 1)],
+        ),
+        (
+            ubig!(2).pow(128) - ubig!(1),
+            vec![
+                (ubig!(3), 1),
+                (ubig!(5), 1),
+                (ubig!(17), 1),
+                (ubig!(257), 1),
+                (ubig!(641), 1),
+                (ubig!(65537), 1),
+                (ubig!(274177), 1),
+                (ubig!(6700417), 1),
+                (ubig!(67280421310721), 1),
+            ],
+        ),
+        (
+            ubig!(1000000007).pow(3) * ubig!(998244353).pow(2),
+            vec![(ubig!(998244353), 2), (ubig!(1000000007), 3)],
+        ),
+        // A prime: 2^127 - 1.
+        (
+            ubig!(2).pow(127) - ubig!(1),
+            vec![(ubig!(2).pow(127) - ubig!(1), 1)],
+        ),
+        // Carmichael number.
+        (
+            ubig!(561),
+            vec![(ubig!(3), 1), (ubig!(11), 1), (ubig!(17), 1)],
+        ),
+        // Strong pseudoprime to bases 2..=31, with factors above the trial division bound.
+        (
+            ubig!(3825123056546413051),
+            vec![(ubig!(149491), 1), (ubig!(747451), 1), (ubig!(34233211), 1)],
+        ),
+    ];
+
+    for (n, factors) in &test_cases {
+        assert_eq!(n.factor(), *factors);
+        let factorization = n.factor_with_budget(FactorBudget::default());
+        assert!(factorization.is_complete());
+        assert_eq!(factorization.factors(), &factors[..]);
+    }
+}
+
+#[test]
+fn test_factor_with_budget() {
+    let p = ubig!(1000000007);
+    let q = ubig!(998244353);
+    let n = &p * &q * ubig!(12);
+
+    let budget = FactorBudget {
+        trial_division_bound: 10,
+        pm1_bound: 0,
+        rho_iterations: 0,
+    };
+    let factorization = n.factor_with_budget(budget);
+    assert!(!factorization.is_complete());
+    assert_eq!(factorization.factors(), [(ubig!(2), 2), (ubig!(3), 1)]);
+    assert_eq!(factorization.unfactored(), [(&p * &q, 1)]);
+
+    // 998244353 - 1 = 2^23 * 7 * 17
+    let budget = FactorBudget {
+        trial_division_bound: 10,
+        pm1_bound: 1 << 23,
+        rho_iterations: 0,
+    };
+    let factorization = n.factor_with_budget(budget);
+    assert!(factorization.is_complete());
+    assert_eq!(
+        factorization.factors(),
+        [(ubig!(2), 2), (ubig!(3), 1), (q.clone(), 1), (p.clone(), 1)]
+    );
+
+    let budget = FactorBudget {
+        trial_division_bound: 0,
+        pm1_bound: 0,
+        rho_iterations: usize::MAX,
+    };
+    let factorization = n.factor_with_budget(budget);
+    assert!(factorization.is_complete());
+    assert_eq!(
+        factorization.into_factors(),
+        [(ubig!(2), 2), (ubig!(3), 1), (q, 1), (p, 1)]
+    );
+}
+
+#[test]
+fn test_factor_large_trial_division_bound() {
+    // Primes on both sides of the sieve segment boundaries, with the largest bound.
+    let budget = FactorBudget {
+        trial_division_bound: u32::MAX,
+        pm1_bound: 0,
+        rho_iterations: 0,
+    };
+    let n = ubig!(32749) * ubig!(32771) * ubig!(32771) * ubig!(65537) * ubig!(1000003);
+    let factorization = n.factor_with_budget(budget);
+    assert!(factorization.is_complete());
+    assert_eq!(
+        factorization.factors(),
+        [
+            (ubig!(32749), 1),
+            (ubig!(32771), 2),
+            (ubig!(65537), 1),
+            (ubig!(1000003), 1)
+        ]
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_factor_0() {
+    let _ = ubig!(0).factor();
+}
@@ -0,0 +1,104 @@
+use ibig::{binomial, double_factorial, factorial, multinomial, ubig, UBig};
+
+fn naive_factorial(n: usize) -> UBig {
+    (1..=n).fold(ubig!(1), |res, i| res * i)
+}
+
+#[test]
+fn test_factorial() {
+    assert_eq!(factorial(0), ubig!(1));
+    assert_eq!(factorial(1), ubig!(1));
+    assert_eq!(factorial(2), ubig!(2));
+    assert_eq!(factorial(10), ubig!(3628800));
+    assert_eq!(factorial(20), ubig!(2432902008176640000));
+    assert_eq!(factorial(30), ubig!(265252859812191058636308480000000));
+
+    for n in 0..300 {
+        assert_eq!(factorial(n), naive_factorial(n));
+    }
+    assert_eq!(factorial(3000), naive_factorial(3000));
+}
+
+#[test]
+fn test_double_factorial() {
+    assert_eq!(double_factorial(0), ubig!(1));
+    assert_eq!(double_factorial(1), ubig!(1));
+    assert_eq!(double_factorial(2), ubig!(2));
+    assert_eq!(double_factorial(9), ubig!(945));
+    assert_eq!(double_factorial(10), ubig!(3840));
+
+    for n in 1..500 {
+        assert_eq!(double_factorial(n) * double_factorial(n - 1), factorial(n));
+    }
+}
+
+#[test]
+fn test_binomial() {
+    assert_eq!(binomial(0, 0), ubig!(1));
+    assert_eq!(binomial(0, 1), ubig!(0));
+    assert_eq!(binomial(10, 3), ubig!(120));
+    assert_eq!(binomial(10, 11), ubig!(0));
+    assert_eq!(binomial(100, 50), ubig!(100891344545564193334812497256));
+
+    // Pascal's triangle.
+    for n in 1..100 {
+        assert_eq!(binomial(n, 0), ubig!(1));
+        assert_eq!(binomial(n, n), ubig!(1));
+        for k in 1..n {
+            assert_eq!(binomial(n, k), binomial(n - 1, k - 1) + binomial(n - 1, k));
+        }
+    }
+
+    let n = 5000;
+    let k = 1234;
+    assert_eq!(
+        binomial(n, k) * factorial(k) * factorial(n - k),
+        factorial(n)
+    );
+}
+
+#[test]
+fn test_binomial_small_k() {
+    fn naive_binomial(n: usize, k: usize) -> UBig {
+        (0..k).fold(ubig!(1), |res, i| res * (n - i)) / naive_factorial(k)
+    }
+
+    // Around the switch between the product and the prime factorization.
+    for (n, ks) in [(1000, 240..270), (10000, 790..810), (100000, 2520..2540)] {
+        for k in ks {
+            assert_eq!(binomial(n, k), naive_binomial(n, k));
+            assert_eq!(binomial(n, n - k), naive_binomial(n, k));
+        }
+    }
+
+    assert_eq!(binomial(200_000_000, 2), ubig!(19999999900000000));
+    assert_eq!(binomial(usize::MAX, 0), ubig!(1));
+    assert_eq!(binomial(usize::MAX, 1), UBig::from(usize::MAX));
+    assert_eq!(binomial(usize::MAX, usize::MAX - 1), UBig::from(usize::MAX));
+    assert_eq!(binomial(usize::MAX, 3), naive_binomial(usize::MAX, 3));
+    assert_eq!(
+        multinomial(&[usize::MAX - 3, 1, 2]),
+        binomial(usize::MAX, 3) * ubig!(3)
+    );
+}
+
+#[test]
+fn test_multinomial() {
+    assert_eq!(multinomial(&[]), ubig!(1));
+    assert_eq!(multinomial(&[0, 0]), ubig!(1));
+    assert_eq!(multinomial(&[5]), ubig!(1));
+    assert_eq!(multinomial(&[3, 4]), binomial(7, 3));
+    // MISSISSIPPI
+    assert_eq!(multinomial(&[1, 4, 4, 2]), ubig!(34650));
+
+    let ks = [100, 200, 0, 1, 300, 7];
+    let n: usize = ks.iter().sum();
+    let denominator = ks.iter().fold(ubig!(1), |res, &k| res * factorial(k));
+    assert_eq!(multinomial(&ks) * denominator, factorial(n));
+}
+
+#[test]
+#[should_panic]
+fn test_multinomial_overflow() {
+    let _ = multinomial(&[usize::MAX, 1]);
+}
@@ -0,0 +1,72 @@
+use ibig::{fibonacci, fibonacci_mod, fibonacci_pair, lucas, modular::ModuloRing, ubig, UBig};
+
+#[test]
+fn test_fibonacci() {
+    let mut a = ubig!(0);
+    let mut b = ubig!(1);
+    for n in 0..1000 {
+        assert_eq!(fibonacci(n), a);
+        assert_eq!(fibonacci_pair(n), (a.clone(), b.clone()));
+        let c = &a + &b;
+        a = b;
+        b = c;
+    }
+
+    assert_eq!(fibonacci(93), ubig!(12200160415121876738));
+    assert_eq!(
+        fibonacci(200),
+        ubig!(_280571172992510140037611932413038677189525)
+    );
+
+    // F(2n) = F(n) L(n)
+    for n in [1000, 12345, 100000] {
+        assert_eq!(fibonacci(2 * n), fibonacci(n) * lucas(n));
+    }
+}
+
+#[test]
+fn test_lucas() {
+    let mut a = ubig!(2);
+    let mut b = ubig!(1);
+    for n in 0..1000 {
+        assert_eq!(lucas(n), a);
+        let c = &a + &b;
+        a = b;
+        b = c;
+    }
+
+    // L(n) = F(n-1) + F(n+1)
+    for n in [1000, 12345, 100000] {
+        assert_eq!(lucas(n), fibonacci(n - 1) + fibonacci(n + 1));
+    }
+}
+
+#[test]
+fn test_fibonacci_mod() {
+    let ring = ModuloRing::new(&ubig!(1));
+    assert_eq!(fibonacci_mod(&ubig!(10), &ring), ring.from(0));
+
+    for m in [
+        ubig!(2),
+        ubig!(1000000007),
+        ubig!(1) << 100,
+        (ubig!(1) << 300) - ubig!(1),
+    ] {
+        let ring = ModuloRing::new(&m);
+        for n in (0usize..500).chain([1000, 12345, 100000]) {
+            assert_eq!(
+                fibonacci_mod(&UBig::from(n), &ring),
+                ring.from(fibonacci(n))
+            );
+        }
+    }
+
+    // The Pisano period of 10^9 + 7 divides 2 * (10^9 + 8).
+    let ring = ModuloRing::new(&ubig!(1000000007));
+    let period = ubig!(2000000016);
+    let n = ubig!(_0x123456789abcdef0123456789abcdef);
+    assert_eq!(
+        fibonacci_mod(&(&period * &n + ubig!(12345)), &ring),
+        fibonacci_mod(&ubig!(12345), &ring)
+    );
+}
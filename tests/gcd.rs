@@ -1,5 +1,5 @@
 use ibig::{
-    ibig,
+    extended_gcd_all, gcd_all, ibig,
     ops::{Abs, UnsignedAbs},
     ubig, IBig, UBig,
 };
@@ -97,6 +97,57 @@ fn test_gcd_ubig() {
     }
 }
 
+fn check_extended_gcd(a: &UBig, b: &UBig, gcd: &UBig) {
+    assert_eq!(a.gcd(b), *gcd);
+    let (g, x, y) = a.extended_gcd(b);
+    assert_eq!(g, *gcd);
+    assert_eq!(&x * IBig::from(a) + &y * IBig::from(b), IBig::from(g));
+    assert!(x.unsigned_abs() <= *b.max(&ubig!(1)));
+    assert!(y.unsigned_abs() <= *a.max(&ubig!(1)));
+}
+
+#[test]
+fn test_gcd_ubig_large() {
+    // Consecutive Fibonacci numbers: all quotients are 1.
+    let mut fib = vec![ubig!(0), ubig!(1)];
+    for i in 2..=30000 {
+        let next = &fib[i - 1] + &fib[i - 2];
+        fib.push(next);
+    }
+    // gcd(F(m), F(n)) = F(gcd(m, n))
+    for (m, n, g) in [
+        (30000, 29999, 1),
+        (30000, 20000, 10000),
+        (29997, 3000, 3),
+        (3000, 2400, 600),
+        (210, 180, 30),
+    ] {
+        check_extended_gcd(&fib[m], &fib[n], &fib[g]);
+        check_extended_gcd(&fib[n], &fib[m], &fib[g]);
+    }
+
+    // gcd(2^m - 1, 2^n - 1) = 2^gcd(m, n) - 1: large quotients.
+    for (m, n, g) in [
+        (100000, 99999, 1),
+        (100000, 60000, 20000),
+        (90000, 300, 300),
+        (9999, 5555, 1111),
+    ] {
+        let a = (ubig!(1) << m) - ubig!(1);
+        let b = (ubig!(1) << n) - ubig!(1);
+        let gcd = (ubig!(1) << g) - ubig!(1);
+        check_extended_gcd(&a, &b, &gcd);
+        check_extended_gcd(&b, &a, &gcd);
+    }
+
+    // Powers of coprime numbers, with a common factor.
+    let c = ubig!(3).pow(5000);
+    let a = ubig!(2).pow(60000) * &c;
+    let b = ubig!(5).pow(30000) * &c;
+    check_extended_gcd(&a, &b, &c);
+    check_extended_gcd(&b, &a, &c);
+}
+
 #[test]
 #[should_panic]
 fn test_gcd_ubig_0_0() {
@@ -143,3 +194,97 @@ fn test_gcd_ibig_0_0() {
 fn test_extended_gcd_ibig_0_0() {
     let _ = ibig!(0).extended_gcd(&ibig!(0));
 }
+
+#[test]
+fn test_lcm_ubig() {
+    let test_cases = [
+        (ubig!(0), ubig!(0), ubig!(0)),
+        (ubig!(0), ubig!(123), ubig!(0)),
+        (ubig!(1), ubig!(123), ubig!(123)),
+        (ubig!(123), ubig!(123), ubig!(123)),
+        (ubig!(4), ubig!(6), ubig!(12)),
+        (ubig!(42), ubig!(56), ubig!(168)),
+        (
+            ubig!(_0x123456789123456789123456789123456789),
+            ubig!(_0x987654321987654321987654321987654321),
+            ubig!(_0x3dad745c086f4a32c086f4a32c086f4a32c049472ed),
+        ),
+    ];
+
+    for (a, b, c) in &test_cases {
+        for (a, b) in [(a, b), (b, a)] {
+            assert_eq!(a.lcm(b), *c);
+            assert_eq!(a.checked_lcm(b), Some(c.clone()));
+        }
+    }
+
+    for a in 1u8..=20 {
+        for b in 1u8..=20 {
+            let a = UBig::from(a);
+            let b = UBig::from(b);
+            assert_eq!(a.lcm(&b) * a.gcd(&b), &a * &b);
+        }
+    }
+}
+
+#[test]
+fn test_lcm_ibig() {
+    assert_eq!(ibig!(12).lcm(&ibig!(18)), ibig!(36));
+    assert_eq!(ibig!(12).lcm(&ibig!(-18)), ibig!(36));
+    assert_eq!(ibig!(-12).lcm(&ibig!(18)), ibig!(36));
+    assert_eq!(ibig!(-12).lcm(&ibig!(-18)), ibig!(36));
+    assert_eq!(ibig!(-12).lcm(&ibig!(0)), ibig!(0));
+    assert_eq!(ibig!(-12).checked_lcm(&ibig!(-18)), Some(ibig!(36)));
+}
+
+#[test]
+fn test_gcd_all() {
+    assert_eq!(gcd_all(Vec::<UBig>::new()), ubig!(0));
+    assert_eq!(gcd_all(&[ubig!(0), ubig!(0)]), ubig!(0));
+    assert_eq!(gcd_all(&[ubig!(0), ubig!(12)]), ubig!(12));
+    assert_eq!(gcd_all(vec![ubig!(12), ubig!(18), ubig!(27)]), ubig!(3));
+    assert_eq!(
+        gcd_all(&[ubig!(1) << 200, ubig!(1) << 100, ubig!(3) << 150]),
+        ubig!(1) << 100
+    );
+
+    // Stops at the first 1.
+    let mut consumed = 0;
+    let numbers = [ubig!(6), ubig!(10), ubig!(15), ubig!(7), ubig!(11)];
+    let g = gcd_all(numbers.iter().inspect(|_| consumed += 1));
+    assert_eq!(g, ubig!(1));
+    assert_eq!(consumed, 3);
+}
+
+#[test]
+fn test_extended_gcd_all() {
+    let test_cases = [
+        vec![],
+        vec![ubig!(0)],
+        vec![ubig!(0), ubig!(0), ubig!(0)],
+        vec![ubig!(0), ubig!(12), ubig!(0)],
+        vec![ubig!(12), ubig!(18), ubig!(27)],
+        vec![ubig!(6), ubig!(10), ubig!(15)],
+        vec![ubig!(1) << 200, ubig!(0), ubig!(1) << 100, ubig!(3) << 150],
+        vec![
+            ubig!(_0x123456789123456789123456789123456789),
+            ubig!(_0x987654321987654321987654321987654321),
+            ubig!(_0x5a4653ca673768565b41f775d6947d55cf3813d1),
+            ubig!(_0x2d00000002d00000002d00000002d),
+        ],
+    ];
+
+    for a in &test_cases {
+        let (g, x) = extended_gcd_all(a);
+        assert_eq!(g, gcd_all(a));
+        assert_eq!(x.len(), a.len());
+        let mut sum = ibig!(0);
+        for (x, a) in x.iter().zip(a) {
+            if *a == ubig!(0) {
+                assert_eq!(*x, ibig!(0));
+            }
+            sum += x * IBig::from(a);
+        }
+        assert_eq!(sum, IBig::from(g));
+    }
+}
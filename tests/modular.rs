@@ -1,4 +1,12 @@
-use ibig::{ibig, modular::ModuloRing, ubig};
+use ibig::{
+    ibig,
+    modular::{Centered, ConstModulo, FixedBasePow, ModPoly, ModuloOwned, ModuloRing},
+    ntt::PRIMES,
+    ops::DivRem,
+    ubig, IBig, UBig,
+};
+use rand::prelude::*;
+use std::{rc::Rc, sync::Arc, thread, time::Instant};
 
 #[test]
 fn test_modulus() {
@@ -232,6 +240,35 @@ fn test_mul() {
     }
 }
 
+#[test]
+fn test_mul_odd_modulus() {
+    // Odd moduli use Montgomery multiplication, both word by word and by multiplication.
+    let moduli = [
+        ubig!(3).pow(50),
+        ubig!(3).pow(300),
+        (ubig!(1) << 256) - ubig!(1),
+        ubig!(3).pow(1500),
+        (ubig!(1) << 4096) - ubig!(1),
+        ubig!(7).pow(2000),
+        ubig!(3).pow(42000),
+    ];
+    for m in &moduli {
+        let ring = ModuloRing::new(m);
+        let a = ubig!(5).pow(m.bit_len()) % m;
+        let b = m - ubig!(2).pow(m.bit_len() / 2);
+        let c = m - ubig!(1);
+
+        assert_eq!(ring.from(&a).residue(), a);
+        assert_eq!(ring.from(&c).residue(), c);
+        assert_eq!((ring.from(&a) * ring.from(&b)).residue(), &a * &b % m);
+        assert_eq!((ring.from(&b) * ring.from(&c)).residue(), &b * &c % m);
+        assert_eq!((ring.from(&c) * ring.from(&c)).residue(), ubig!(1));
+        assert_eq!(ring.from(&a).pow(&ubig!(5)).residue(), a.pow(5) % m);
+        let x = ring.from(&b);
+        assert_eq!(x.inverse().unwrap() * x, ring.from(1));
+    }
+}
+
 #[test]
 fn test_inverse() {
     let ring = ModuloRing::new(&ubig!(1));
@@ -378,6 +415,70 @@ fn test_pow_signed_noninvertible() {
     let _ = ring.from(2).pow_signed(&ibig!(-2));
 }
 
+#[test]
+fn test_pow_with_factorization() {
+    let m61 = ubig!(2).pow(61) - ubig!(1);
+    let m89 = ubig!(2).pow(89) - ubig!(1);
+    let m127 = ubig!(2).pow(127) - ubig!(1);
+    let factorizations = [
+        vec![],
+        vec![(ubig!(3), 2), (ubig!(7), 1)],
+        vec![(m127.clone(), 1)],
+        vec![(m89.clone(), 1), (m127.clone(), 1)],
+        vec![(ubig!(2), 5), (m61.clone(), 3), (m127.clone(), 1)],
+        vec![(ubig!(2), 200)],
+        vec![(ubig!(3), 1), (ubig!(5), 3), (m89, 2)],
+    ];
+    let mut rng = StdRng::seed_from_u64(1);
+    for factors in &factorizations {
+        let ring = ModuloRing::with_factorization(factors);
+        let n = ring.modulus();
+        let plain_ring = ModuloRing::new(&n);
+        assert_eq!(
+            n,
+            factors.iter().fold(ubig!(1), |acc, (p, k)| acc * p.pow(*k))
+        );
+
+        let mut bases = vec![ubig!(0), ubig!(1), &n - ubig!(1)];
+        for (p, _) in factors {
+            bases.push(p.clone());
+            bases.push(&n / p);
+        }
+        bases.push(rng.gen_range(ubig!(0)..n.clone()));
+        let mut exps = vec![ubig!(0), ubig!(1), ubig!(2), ubig!(3), ubig!(250)];
+        exps.push(rng.gen_range(ubig!(0)..&n * &n));
+        exps.push(rng.gen_range(ubig!(0)..ubig!(2).pow(1000)));
+
+        for base in &bases {
+            for exp in &exps {
+                assert_eq!(
+                    ring.from(base).pow(exp).residue(),
+                    plain_ring.from(base).pow(exp).residue()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_with_factorization_not_prime() {
+    let _ = ModuloRing::with_factorization(&[(ubig!(2).pow(127) + ubig!(1), 1)]);
+}
+
+#[test]
+#[should_panic]
+fn test_with_factorization_zero_exponent() {
+    let _ = ModuloRing::with_factorization(&[(ubig!(2).pow(127) - ubig!(1), 0)]);
+}
+
+#[test]
+#[should_panic]
+fn test_with_factorization_repeated_prime() {
+    let p = ubig!(2).pow(127) - ubig!(1);
+    let _ = ModuloRing::with_factorization(&[(p.clone(), 1), (p, 1)]);
+}
+
 #[test]
 fn test_format() {
     let ring = ModuloRing::new(&ubig!(100));
@@ -417,3 +518,668 @@ fn test_format() {
         "C9F2C9CD04674EDEA3FFFFFFF (mod C9F2C9CD04674EDEA40000000)"
     );
 }
+
+#[test]
+fn test_owned() {
+    for m in [ubig!(100), ubig!(10).pow(100), ubig!(3).pow(100)] {
+        let ring = Rc::new(ModuloRing::new(&m));
+        let x = ModuloOwned::new(ring.clone(), 123);
+        let y = ModuloOwned::new(ring.clone(), ibig!(-45));
+        let (a, b) = (ring.from(123), ring.from(ibig!(-45)));
+
+        assert_eq!((&x + &y).to_modulo(), &a + &b);
+        assert_eq!((&x - &y).to_modulo(), &a - &b);
+        assert_eq!((&x * &y).to_modulo(), &a * &b);
+        assert_eq!((-&x).to_modulo(), -&a);
+        assert_eq!(x.clone() + y.clone(), &x + &y);
+        assert_eq!(x.clone() - &y, &x - y.clone());
+        let mut z = x.clone();
+        z *= &y;
+        z += y.clone();
+        assert_eq!(z.to_modulo(), &a * &b + &b);
+        assert_eq!(x.pow(&ubig!(7)).to_modulo(), a.pow(&ubig!(7)));
+        assert_eq!(x.pow_signed(&ibig!(3)).to_modulo(), a.pow_signed(&ibig!(3)));
+        assert_eq!(x.residue(), a.residue());
+        assert_eq!(format!("{}", x), format!("{}", a));
+        assert_eq!(format!("{:x}", y), format!("{:x}", b));
+
+        let inv = ModuloOwned::new(ring.clone(), 7).inverse().unwrap();
+        assert_eq!(inv.to_modulo(), ring.from(7).inverse().unwrap());
+        assert_eq!(&x / &inv, x.clone() * ModuloOwned::new(ring.clone(), 7));
+        assert_eq!(ModuloOwned::from_modulo(ring.clone(), &a * &b), x * y);
+    }
+}
+
+#[test]
+fn test_owned_send() {
+    let ring = Arc::new(ModuloRing::new(&ubig!(10).pow(50)));
+    let x = ModuloOwned::new(ring.clone(), 12345);
+    let y = thread::spawn(move || x.pow(&ubig!(3))).join().unwrap();
+    assert_eq!(y, ModuloOwned::new(ring, ubig!(12345).pow(3)));
+}
+
+#[test]
+fn test_owned_static() {
+    let ring: &'static ModuloRing = Box::leak(Box::new(ModuloRing::new(&ubig!(100))));
+    let x = ModuloOwned::new(ring, 7);
+    assert_eq!((x.clone() * x).residue(), ubig!(49));
+}
+
+#[test]
+#[should_panic]
+fn test_owned_different_rings() {
+    let ring1 = Rc::new(ModuloRing::new(&ubig!(100)));
+    let ring2 = Rc::new(ModuloRing::new(&ubig!(100)));
+    let _ = ModuloOwned::new(ring1, 1) + ModuloOwned::new(ring2, 1);
+}
+
+#[test]
+#[should_panic]
+fn test_owned_from_modulo_different_ring() {
+    let ring1 = Rc::new(ModuloRing::new(&ubig!(100)));
+    let ring2 = ModuloRing::new(&ubig!(100));
+    let _ = ModuloOwned::from_modulo(ring1, ring2.from(1));
+}
+
+fn check_const_modulo<const M: u64>() {
+    let ring = ModuloRing::new(&UBig::from(M));
+    let values = [0, 1, 2, 3, 12345, M / 2, M - 1, u64::MAX];
+    for &a in &values {
+        let x = ConstModulo::<M>::new(a);
+        let xr = ring.from(a);
+        assert_eq!(ConstModulo::<M>::modulus(), M);
+        assert_eq!(UBig::from(x.residue()), xr.residue());
+        assert_eq!(
+            ConstModulo::<M>::from(UBig::from(a) * UBig::from(M) + UBig::from(a)),
+            x
+        );
+        assert_eq!(ConstModulo::<M>::from(-IBig::from(a)), -x);
+        assert_eq!(UBig::from((-x).residue()), (-&xr).residue());
+        assert_eq!(
+            UBig::from(x.pow(&UBig::from(12837918273u64)).residue()),
+            xr.pow(&UBig::from(12837918273u64)).residue()
+        );
+        assert_eq!(
+            x.inverse().map(|y| UBig::from(y.residue())),
+            xr.inverse().map(|y| y.residue())
+        );
+        assert_eq!(format!("{}", x), format!("{}", xr));
+        assert_eq!(format!("{:?}", x), format!("{:?}", xr));
+        for &b in &values {
+            let y = ConstModulo::<M>::new(b);
+            let yr = ring.from(b);
+            assert_eq!(UBig::from((x + y).residue()), (&xr + &yr).residue());
+            assert_eq!(UBig::from((x - y).residue()), (&xr - &yr).residue());
+            assert_eq!(UBig::from((x * y).residue()), (&xr * &yr).residue());
+            if let Some(inv) = yr.inverse() {
+                assert_eq!(UBig::from((x / y).residue()), (&xr * inv).residue());
+            }
+            let mut z = x;
+            z += y;
+            z *= y;
+            z -= x;
+            assert_eq!(UBig::from(z.residue()), ((&xr + &yr) * &yr - &xr).residue());
+        }
+    }
+}
+
+/// Bits in a machine word, which depends on the target: the NTT primes use the top bit.
+const WORD_BITS: u32 = u64::BITS - PRIMES[2].leading_zeros();
+
+#[test]
+fn test_const_modulo() {
+    check_const_modulo::<1>();
+    check_const_modulo::<100>();
+    check_const_modulo::<{ PRIMES[0] }>();
+    check_const_modulo::<{ u64::MAX >> (u64::BITS - WORD_BITS) }>();
+    check_const_modulo::<{ 1 << (WORD_BITS - 1) }>();
+
+    const X: ConstModulo<100> = ConstModulo::new(1234);
+    assert_eq!(X.residue(), 34);
+    assert_eq!(
+        std::mem::size_of::<ConstModulo<{ PRIMES[0] }>>() * 8,
+        WORD_BITS as usize
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_const_modulo_div_by_noninvertible() {
+    let _ = ConstModulo::<100>::new(3) / ConstModulo::<100>::new(10);
+}
+
+#[test]
+fn test_batch_inverse() {
+    for m in [ubig!(1000), ubig!(10).pow(40), ubig!(3).pow(100)] {
+        let ring = ModuloRing::new(&m);
+        let mut values: Vec<_> = (0..20u32)
+            .map(|i| ring.from(ubig!(7).pow(i as usize * 3) + ubig!(11)))
+            .filter(|x| x.inverse().is_some())
+            .collect();
+        let expected: Vec<_> = values.iter().map(|x| x.inverse().unwrap()).collect();
+        assert_eq!(ring.batch_inverse(&mut values), Ok(()));
+        assert_eq!(values, expected);
+
+        let mut values = vec![ring.from(1); 1];
+        assert_eq!(ring.batch_inverse(&mut values), Ok(()));
+        assert_eq!(values, [ring.from(1)]);
+
+        let mut values: Vec<_> = (1..10).map(|i| ring.from(ubig!(7).pow(i))).collect();
+        values[6] = ring.from(30);
+        values[8] = ring.from(0);
+        let original = values.clone();
+        assert_eq!(ring.batch_inverse(&mut values), Err(6));
+        assert_eq!(values, original);
+    }
+
+    let ring = ModuloRing::new(&ubig!(100));
+    assert_eq!(ring.batch_inverse(&mut []), Ok(()));
+    assert_eq!(ring.batch_inverse(&mut [ring.from(0)]), Err(0));
+    assert_eq!(
+        ring.batch_inverse(&mut [ring.from(2), ring.from(3), ring.from(5)]),
+        Err(0)
+    );
+    assert_eq!(
+        ring.batch_inverse(&mut [ring.from(3), ring.from(7), ring.from(5)]),
+        Err(2)
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_batch_inverse_different_rings() {
+    let ring1 = ModuloRing::new(&ubig!(100));
+    let ring2 = ModuloRing::new(&ubig!(100));
+    let mut values = [ring1.from(1), ring2.from(1)];
+    let _ = ring1.batch_inverse(&mut values);
+}
+
+#[test]
+fn test_multi_pow() {
+    for m in [ubig!(1000000007), ubig!(10).pow(40), ubig!(3).pow(100)] {
+        let ring = ModuloRing::new(&m);
+        let g = ring.from(5);
+        let h = ring.from(&m - ubig!(7));
+        let k = ring.from(ubig!(123456789).pow(5));
+        let exps = [
+            ubig!(0),
+            ubig!(1),
+            ubig!(2),
+            ubig!(0b1011011101),
+            ubig!(_987654321987654321987654321),
+            ubig!(1) << 300,
+            (ubig!(1) << 500) - ubig!(1),
+        ];
+        assert_eq!(ring.multi_pow(&[]), ring.from(1));
+        for a in &exps {
+            assert_eq!(ring.multi_pow(&[(&g, a)]), g.pow(a));
+            for b in &exps {
+                assert_eq!(ring.multi_pow(&[(&g, a), (&h, b)]), g.pow(a) * h.pow(b));
+                assert_eq!(
+                    ring.multi_pow(&[(&g, a), (&h, b), (&k, &exps[4]), (&g, b)]),
+                    g.pow(a) * h.pow(b) * k.pow(&exps[4]) * g.pow(b)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_multi_pow_different_rings() {
+    let ring1 = ModuloRing::new(&ubig!(100));
+    let ring2 = ModuloRing::new(&ubig!(100));
+    let _ = ring1.multi_pow(&[(&ring1.from(2), &ubig!(3)), (&ring2.from(2), &ubig!(3))]);
+}
+
+#[test]
+fn test_fixed_base_pow() {
+    for m in [ubig!(1000000007), ubig!(10).pow(40), ubig!(3).pow(100)] {
+        let ring = ModuloRing::new(&m);
+        let g = ring.from(&m - ubig!(5));
+        for max_exp_bits in [0, 1, 2, 7, 64, 300, 1000] {
+            let g_pow = FixedBasePow::new(&g, max_exp_bits);
+            for exp in [
+                ubig!(0),
+                ubig!(1),
+                ubig!(5),
+                ubig!(0b1011011101),
+                (ubig!(1) << 63) + ubig!(1),
+                ubig!(_987654321987654321987654321),
+                (ubig!(1) << 300) - ubig!(1),
+                ubig!(3).pow(600),
+            ] {
+                assert_eq!(g_pow.pow(&exp), g.pow(&exp));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pow_ct() {
+    for m in [
+        ubig!(1),
+        ubig!(100),
+        ubig!(1000000007),
+        ubig!(0xffffffffffffffc5),
+        ubig!(10).pow(40) + ubig!(1),
+        ubig!(3).pow(100),
+        ubig!(2).pow(607) - ubig!(1),
+        ubig!(3).pow(1000),
+        // Even moduli longer than a word, without a Montgomery representation.
+        ubig!(10).pow(40),
+        ubig!(1) << 64,
+        ubig!(1) << 127,
+        (ubig!(1) << 600) - ubig!(2),
+        ubig!(3).pow(1000) * ubig!(2),
+    ] {
+        let ring = ModuloRing::new(&m);
+        for x in [
+            ubig!(0),
+            ubig!(1),
+            ubig!(2),
+            &m - ubig!(1),
+            ubig!(7).pow(300),
+        ] {
+            let x = ring.from(x);
+            for exp in [
+                ubig!(0),
+                ubig!(1),
+                ubig!(2),
+                ubig!(0b1011011101),
+                (ubig!(1) << 63) + ubig!(1),
+                ubig!(_987654321987654321987654321),
+                (ubig!(1) << 300) - ubig!(1),
+                ubig!(3).pow(1500),
+            ] {
+                assert_eq!(x.pow_ct(&exp), x.pow(&exp));
+            }
+        }
+    }
+}
+
+/// Welch's t-statistic comparing the means of two samples.
+fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+    let mean_var = |v: &[f64]| {
+        let n = v.len() as f64;
+        let mean = v.iter().sum::<f64>() / n;
+        let var = v.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+        (mean, var / n)
+    };
+    let (mean_a, var_a) = mean_var(a);
+    let (mean_b, var_b) = mean_var(b);
+    (mean_a - mean_b) / (var_a + var_b).sqrt()
+}
+
+/// Timing test in the style of dudect: compare the running time of `pow_ct` modulo `m` for a fixed
+/// exponent with random exponents.
+///
+/// With a leaky implementation such as `pow`, the t-statistic is in the hundreds. Wall-clock
+/// measurements are too noisy for the default test run, so the tests using this are ignored; run
+/// them in release mode on a quiet machine with
+/// `cargo test --release --test modular -- --ignored test_pow_ct_timing`.
+fn check_pow_ct_timing(m: &UBig) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let ring = ModuloRing::new(m);
+    let x = ring.from(ubig!(_12345678901234567890123456789));
+    let fixed = ubig!(0);
+
+    // Generate all the inputs up front, so that only `pow_ct` is timed.
+    let inputs: Vec<(usize, UBig)> = (0..100000)
+        .map(|_| {
+            let class = rng.gen_range(0..2);
+            let exp = if class == 0 {
+                fixed.clone()
+            } else {
+                rng.gen_range(ubig!(0)..m.clone())
+            };
+            (class, exp)
+        })
+        .collect();
+
+    let mut samples: [Vec<f64>; 2] = [Vec::new(), Vec::new()];
+    for (class, exp) in &inputs {
+        let start = Instant::now();
+        let res = x.pow_ct(exp);
+        let elapsed = start.elapsed();
+        drop(res);
+        samples[*class].push(elapsed.as_secs_f64());
+    }
+
+    // Discard outliers caused by interrupts and scheduling.
+    let mut all: Vec<f64> = samples.iter().flatten().cloned().collect();
+    all.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let cutoff = all[all.len() * 9 / 10];
+    let [a, b] = samples.map(|v| v.into_iter().filter(|&t| t < cutoff).collect::<Vec<_>>());
+
+    let t = welch_t(&a, &b);
+    assert!(t.abs() < 10.0, "t = {}", t);
+}
+
+#[test]
+#[ignore]
+fn test_pow_ct_timing_small() {
+    check_pow_ct_timing(&UBig::from(PRIMES[2]));
+}
+
+#[test]
+#[ignore]
+fn test_pow_ct_timing_montgomery() {
+    check_pow_ct_timing(&(ubig!(2).pow(255) - ubig!(19)));
+}
+
+#[test]
+#[ignore]
+fn test_pow_ct_timing_barrett() {
+    check_pow_ct_timing(&(ubig!(2).pow(255) - ubig!(20)));
+}
+
+#[test]
+fn test_centered_residue() {
+    for m in [
+        ubig!(1),
+        ubig!(2),
+        ubig!(7),
+        ubig!(100),
+        ubig!(0xffffffffffffffff),
+        ubig!(10).pow(40),
+        ubig!(3).pow(100),
+    ] {
+        let ring = ModuloRing::new(&m);
+        let half = &m >> 1;
+        let low = if &half << 1 == m {
+            -IBig::from(&half) + ibig!(1)
+        } else {
+            -IBig::from(&half)
+        };
+        let high = IBig::from(&half);
+        for x in [
+            low.clone(),
+            &low + ibig!(1),
+            ibig!(-1),
+            ibig!(0),
+            ibig!(1),
+            &high - ibig!(1),
+            high.clone(),
+        ] {
+            if x < low || x > high {
+                continue;
+            }
+            let a = ring.from_centered(&x);
+            assert_eq!(a, ring.from(&x));
+            assert_eq!(a.centered_residue(), x);
+            assert_eq!(ring.from(Centered(x.clone())), a);
+            let owned = ModuloOwned::new(Rc::new(ModuloRing::new(&m)), Centered(&x));
+            assert_eq!(owned.centered_residue(), x);
+        }
+        // Values just outside the range wrap around.
+        assert_eq!(ring.from(&high + ibig!(1)).centered_residue(), low);
+        assert_eq!(ring.from(&low - ibig!(1)).centered_residue(), high);
+        assert_eq!(ring.from(&m).centered_residue(), ibig!(0));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_from_centered_out_of_range() {
+    let ring = ModuloRing::new(&ubig!(100));
+    let _ = ring.from_centered(&ibig!(-50));
+}
+
+#[test]
+#[should_panic]
+fn test_centered_into_modulo_out_of_range() {
+    let ring = ModuloRing::new(&ubig!(101));
+    let _ = ring.from(Centered(ibig!(51)));
+}
+
+fn random_poly<'a>(ring: &'a ModuloRing, len: usize, rng: &mut StdRng) -> ModPoly<'a> {
+    let m = ring.modulus();
+    ModPoly::new(ring, (0..len).map(|_| rng.gen_range(ubig!(0)..m.clone())))
+}
+
+/// Schoolbook product, as the reference for the faster algorithms.
+fn naive_mul<'a>(a: &ModPoly<'a>, b: &ModPoly<'a>) -> ModPoly<'a> {
+    let ring = a.ring();
+    if a.is_zero() || b.is_zero() {
+        return ModPoly::zero(ring);
+    }
+    let mut res = vec![ring.from(0); a.coeffs().len() + b.coeffs().len() - 1];
+    for (i, x) in a.coeffs().iter().enumerate() {
+        for (j, y) in b.coeffs().iter().enumerate() {
+            res[i + j] += x * y;
+        }
+    }
+    ModPoly::from_coeffs(ring, res)
+}
+
+/// Moduli for polynomial tests: NTT-friendly primes of one and four words, and others.
+fn poly_test_moduli() -> Vec<UBig> {
+    vec![
+        ubig!(998244353),
+        ubig!(1000000007),
+        ubig!(_0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001),
+        ubig!(2).pow(127) - ubig!(1),
+    ]
+}
+
+#[test]
+fn test_mod_poly_basic() {
+    let ring = ModuloRing::new(&ubig!(7));
+    let a = ModPoly::new(&ring, [1, 2, 7, 14]);
+    assert_eq!(a.degree(), Some(1));
+    assert_eq!(a.coeffs(), [ring.from(1), ring.from(2)]);
+    assert_eq!(a.leading_coeff(), Some(&ring.from(2)));
+    let zero = ModPoly::zero(&ring);
+    assert!(zero.is_zero());
+    assert_eq!(zero.degree(), None);
+    assert_eq!(zero.leading_coeff(), None);
+    assert_eq!(ModPoly::new(&ring, [0, 0]), zero);
+    assert_eq!(
+        ModPoly::from_coeffs(&ring, vec![ring.from(1), ring.from(2)]),
+        a
+    );
+
+    let b = ModPoly::new(&ring, [3, 5, 1]);
+    assert_eq!(&a + &b, ModPoly::new(&ring, [4, 0, 1]));
+    assert_eq!(&a - &b, ModPoly::new(&ring, [5, 4, 6]));
+    assert_eq!(-&a, ModPoly::new(&ring, [6, 5]));
+    assert_eq!(&a - &a, zero);
+    let mut c = a.clone();
+    c += &b;
+    c -= b.clone();
+    assert_eq!(c, a);
+    c *= &b;
+    assert_eq!(c, naive_mul(&a, &b));
+
+    assert_eq!(format!("{}", b), "1*x^2 + 5*x + 3 (mod 7)");
+    assert_eq!(format!("{:?}", ModPoly::new(&ring, [0, 3])), "3*x (mod 7)");
+    assert_eq!(format!("{}", zero), "0 (mod 7)");
+    assert_eq!(
+        format!(
+            "{:x}",
+            ModPoly::new(&ModuloRing::new(&ubig!(100)), [10, 0, 20])
+        ),
+        "14*x^2 + a (mod 64)"
+    );
+
+    // Leading coefficients multiply to zero modulo a composite.
+    let ring = ModuloRing::new(&ubig!(6));
+    let a = ModPoly::new(&ring, [1, 2]);
+    let b = ModPoly::new(&ring, [1, 3]);
+    assert_eq!((&a * &b).degree(), Some(1));
+}
+
+#[test]
+fn test_mod_poly_mul() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for m in poly_test_moduli() {
+        let ring = ModuloRing::new(&m);
+        for (len_a, len_b) in [
+            (0, 5),
+            (1, 1),
+            (5, 3),
+            (31, 31),
+            (32, 33),
+            (40, 100),
+            (64, 64),
+            (65, 130),
+            (150, 300),
+        ] {
+            let a = random_poly(&ring, len_a, &mut rng);
+            let b = random_poly(&ring, len_b, &mut rng);
+            let expected = naive_mul(&a, &b);
+            assert_eq!(&a * &b, expected);
+            assert_eq!(&b * &a, expected);
+        }
+    }
+
+    // A composite modulus of the form k * 2^8 + 1 with no suitable root of unity, and the ring
+    // with one element.
+    for m in [ubig!(257) * ubig!(65537), ubig!(1)] {
+        let ring = ModuloRing::new(&m);
+        let a = random_poly(&ring, 100, &mut rng);
+        let b = random_poly(&ring, 120, &mut rng);
+        assert_eq!(&a * &b, naive_mul(&a, &b));
+    }
+}
+
+#[test]
+fn test_mod_poly_div_rem() {
+    let mut rng = StdRng::seed_from_u64(2);
+    for m in poly_test_moduli() {
+        let ring = ModuloRing::new(&m);
+        for (len_a, len_b) in [
+            (0, 1),
+            (3, 5),
+            (5, 1),
+            (10, 4),
+            (100, 70),
+            (300, 100),
+            (250, 200),
+        ] {
+            let a = random_poly(&ring, len_a, &mut rng);
+            let b = random_poly(&ring, len_b, &mut rng);
+            let (q, r) = (&a).div_rem(&b);
+            assert_eq!(&q * &b + &r, a);
+            assert!(r.degree() < b.degree());
+            assert_eq!(&a / &b, q);
+            assert_eq!(&a % &b, r);
+        }
+    }
+
+    // A non-monic divisor modulo a composite.
+    let ring = ModuloRing::new(&ubig!(100));
+    let a = ModPoly::new(&ring, [5, 6, 7, 8]);
+    let b = ModPoly::new(&ring, [1, 3]);
+    let (q, r) = a.clone().div_rem(b.clone());
+    assert_eq!(q * b + r, a);
+}
+
+#[test]
+#[should_panic]
+fn test_mod_poly_div_by_zero() {
+    let ring = ModuloRing::new(&ubig!(7));
+    let _ = ModPoly::new(&ring, [1, 2]) / ModPoly::zero(&ring);
+}
+
+#[test]
+#[should_panic]
+fn test_mod_poly_div_non_invertible_leading_coeff() {
+    let ring = ModuloRing::new(&ubig!(100));
+    let _ = ModPoly::new(&ring, [1, 2, 3]) % ModPoly::new(&ring, [1, 2]);
+}
+
+#[test]
+#[should_panic]
+fn test_mod_poly_different_rings() {
+    let ring1 = ModuloRing::new(&ubig!(7));
+    let ring2 = ModuloRing::new(&ubig!(7));
+    let _ = ModPoly::new(&ring1, [1]) + ModPoly::new(&ring2, [1]);
+}
+
+#[test]
+fn test_mod_poly_gcd() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let ring = ModuloRing::new(&ubig!(998244353));
+    let zero = ModPoly::zero(&ring);
+    let one = ModPoly::new(&ring, [1]);
+    assert_eq!(zero.gcd(&zero), zero);
+    let a = ModPoly::new(&ring, [4, 6]);
+    assert_eq!(
+        a.gcd(&zero),
+        ModPoly::from_coeffs(&ring, vec![&ring.from(4) / &ring.from(6), ring.from(1)])
+    );
+    assert_eq!(zero.gcd(&a), a.gcd(&zero));
+
+    for (len_g, len_a, len_b) in [(1, 1, 1), (2, 5, 3), (10, 20, 30), (40, 100, 80)] {
+        let g = random_poly(&ring, len_g, &mut rng);
+        let a = random_poly(&ring, len_a, &mut rng);
+        let b = random_poly(&ring, len_b, &mut rng);
+        let d = (&a * &g).gcd(&(&b * &g));
+        assert_eq!(d.leading_coeff(), Some(&ring.from(1)));
+        assert_eq!(&(&a * &g) % &d, zero);
+        assert_eq!(&(&b * &g) % &d, zero);
+        assert_eq!(&d % &g, zero);
+        // Random a and b are coprime here.
+        assert_eq!(a.gcd(&b), one);
+    }
+}
+
+#[test]
+fn test_mod_poly_evaluate_interpolate() {
+    let mut rng = StdRng::seed_from_u64(4);
+    for m in poly_test_moduli() {
+        let ring = ModuloRing::new(&m);
+        for (len, num_points) in [(0, 3), (5, 0), (3, 10), (70, 65), (150, 200), (300, 100)] {
+            let p = random_poly(&ring, len, &mut rng);
+            let points: Vec<_> = (0..num_points)
+                .map(|_| ring.from(rng.gen_range(ubig!(0)..m.clone())))
+                .collect();
+            let values = p.evaluate_many(&points);
+            assert_eq!(values.len(), num_points);
+            for (x, v) in points.iter().zip(&values) {
+                assert_eq!(p.evaluate(x), *v);
+            }
+            if len <= num_points {
+                assert_eq!(ModPoly::interpolate(&ring, &points, &values), p);
+            }
+        }
+    }
+
+    let ring = ModuloRing::new(&ubig!(1));
+    let points = [ring.from(0), ring.from(0)];
+    assert!(ModPoly::interpolate(&ring, &points, &points).is_zero());
+}
+
+#[test]
+#[should_panic]
+fn test_mod_poly_interpolate_repeated_points() {
+    let ring = ModuloRing::new(&ubig!(7));
+    let points = [ring.from(1), ring.from(2), ring.from(8)];
+    let values = [ring.from(1), ring.from(2), ring.from(3)];
+    let _ = ModPoly::interpolate(&ring, &points, &values);
+}
+
+/// Sizes large enough for Newton division and the subproduct tree.
+#[test]
+fn test_mod_poly_large() {
+    let mut rng = StdRng::seed_from_u64(5);
+    // 7681 = 15 * 2^9 + 1 only has roots of unity for the shorter products.
+    for m in [ubig!(998244353), ubig!(1000000007), ubig!(7681)] {
+        let ring = ModuloRing::new(&m);
+        let a = random_poly(&ring, 1000, &mut rng);
+        let b = random_poly(&ring, 400, &mut rng);
+        let (q, r) = (&a).div_rem(&b);
+        assert_eq!(&q * &b + &r, a);
+        assert!(r.degree() < b.degree());
+
+        let p = random_poly(&ring, 1100, &mut rng);
+        let points: Vec<_> = (0..1100).map(|i| ring.from(i)).collect();
+        let values = p.evaluate_many(&points);
+        for (x, v) in points.iter().zip(&values).step_by(100) {
+            assert_eq!(p.evaluate(x), *v);
+        }
+        assert_eq!(ModPoly::interpolate(&ring, &points, &values), p);
+    }
+}
@@ -147,3 +147,84 @@ fn test_mul_ibig_primitive() {
     x *= &-2;
     assert_eq!(x, ibig!(12));
 }
+
+#[test]
+fn test_square_ubig() {
+    assert_eq!(ubig!(0).square(), ubig!(0));
+    assert_eq!(ubig!(0xffffffff).square(), ubig!(0xfffffffe00000001));
+    assert_eq!(
+        ubig!(_0x123456789123456789123456789123456789).square(),
+        ubig!(_0x14b66dc32992662d751d9657eb7a2064cff9fd09668d7789971794f4297c6526fb98751)
+    );
+
+    // Lengths around the thresholds of every squaring algorithm.
+    for bits in (1..=12).map(|k| 50 * 2usize.pow(k)) {
+        let ones = (ubig!(1) << bits) - ubig!(1);
+        assert_eq!(ones.square(), &ones * &ones.clone());
+        assert_eq!(
+            ones.square(),
+            (ubig!(1) << (2 * bits)) - (ubig!(1) << (bits + 1)) + ubig!(1)
+        );
+
+        let x = ubig!(3).pow(bits) >> 1;
+        let y = x.clone();
+        assert_eq!(x.square(), &x * &y);
+        assert_eq!(&x * &x, &x * &y);
+        assert_eq!(x.pow(2), &x * &y);
+    }
+}
+
+#[test]
+fn test_mul_ubig_large() {
+    let p = ubig!(1000000007);
+    // Lengths in bits around the thresholds of every algorithm, balanced and unbalanced.
+    let lens = [
+        1000, 1600, 2000, 3000, 12000, 13000, 20000, 60000, 65000, 100000, 300000,
+    ];
+    for (i, &len_a) in lens.iter().enumerate() {
+        for &len_b in &lens[..=i] {
+            let a = (ubig!(3).pow(len_a * 10 / 16) >> 1) + ubig!(1);
+            let b = ubig!(7).pow(len_b * 10 / 28) - ubig!(1);
+            let c = &a * &b;
+            assert_eq!(&b * &a, c);
+            assert_eq!(c.clone() % &a, ubig!(0));
+            assert_eq!(c.clone() / &b, a);
+            assert_eq!(c % &p, (&a % &p) * (&b % &p) % &p);
+
+            let ones_a = (ubig!(1) << len_a) - ubig!(1);
+            let ones_b = (ubig!(1) << len_b) - ubig!(1);
+            assert_eq!(
+                &ones_a * &ones_b,
+                (ubig!(1) << (len_a + len_b)) - (ubig!(1) << len_a) - (ubig!(1) << len_b)
+                    + ubig!(1)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_mul_ubig_very_unbalanced() {
+    let p = ubig!(1000000007);
+    // The longer factor is split into chunks sharing the evaluations of the shorter one, with
+    // remainders shorter than, longer than and equal to zero chunks.
+    for &(len_a, len_b) in &[
+        (200 * 64 * 10, 200 * 64),
+        (200 * 64 * 10 + 150 * 64, 200 * 64),
+        (200 * 64 * 10 + 300 * 64, 200 * 64),
+        (1000 * 64 * 12 + 7, 1000 * 64 - 5),
+    ] {
+        let a = (ubig!(3).pow(len_a * 10 / 16) >> 1) + ubig!(1);
+        let b = ubig!(7).pow(len_b * 10 / 28) - ubig!(1);
+        let c = &a * &b;
+        assert_eq!(&b * &a, c);
+        assert_eq!(c.clone() / &a, b);
+        assert_eq!(c % &p, (&a % &p) * (&b % &p) % &p);
+
+        let ones_a = (ubig!(1) << len_a) - ubig!(1);
+        let ones_b = (ubig!(1) << len_b) - ubig!(1);
+        assert_eq!(
+            &ones_a * &ones_b,
+            (ubig!(1) << (len_a + len_b)) - (ubig!(1) << len_a) - (ubig!(1) << len_b) + ubig!(1)
+        );
+    }
+}
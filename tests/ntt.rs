@@ -0,0 +1,166 @@
+use ibig::{
+    modular::ConstModulo,
+    ntt::{self, Ntt, PRIMES},
+};
+use rand::prelude::*;
+
+// NTT-friendly primes that fit in a word on all targets.
+// 12289 = 3 * 2^12 + 1
+const P: u64 = 12289;
+
+fn naive_convolution(a: &[u64], b: &[u64], m: u64) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut res = vec![0u128; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (r, &y) in res[i..].iter_mut().zip(b) {
+            *r = (*r + (x % m) as u128 * (y % m) as u128) % m as u128;
+        }
+    }
+    res.into_iter().map(|x| x as u64).collect()
+}
+
+fn random_values<const M: u64>(len: usize, rng: &mut impl Rng) -> Vec<ConstModulo<M>> {
+    (0..len).map(|_| ConstModulo::new(rng.gen())).collect()
+}
+
+#[test]
+fn test_max_order() {
+    assert_eq!(Ntt::<P>::max_order(), 12);
+    assert_eq!(Ntt::<65519>::max_order(), 1);
+    assert_eq!(Ntt::<2>::max_order(), 0);
+    assert!(Ntt::<{ PRIMES[0] }>::max_order() >= 12);
+    assert!(Ntt::<{ PRIMES[1] }>::max_order() >= 12);
+    assert!(Ntt::<{ PRIMES[2] }>::max_order() >= 12);
+}
+
+#[test]
+fn test_new() {
+    for order in 0..=12 {
+        let plan = Ntt::<P>::new(order).unwrap();
+        assert_eq!(plan.order(), order);
+    }
+    assert!(Ntt::<P>::new(13).is_none());
+    assert!(Ntt::<65519>::new(1).is_some());
+    assert!(Ntt::<65519>::new(2).is_none());
+    assert!(Ntt::<257>::new(8).is_some());
+    // 161 = 5 * 2^5 + 1 = 7 * 23 has no root of unity of order 2^5.
+    assert!(Ntt::<161>::new(5).is_none());
+}
+
+fn check_forward_naive<const M: u64>(order: u32, rng: &mut impl Rng) {
+    let len = 1usize << order;
+    let plan = Ntt::<M>::new(order).unwrap();
+    let a = random_values::<M>(len, rng);
+    let mut fa = a.clone();
+    plan.forward(&mut fa);
+
+    // The root is fa of the sequence [0, 1, 0, ...].
+    let root = if len > 1 {
+        let mut e = vec![ConstModulo::<M>::new(0); len];
+        e[1] = ConstModulo::new(1);
+        plan.forward(&mut e);
+        e[1]
+    } else {
+        ConstModulo::new(1)
+    };
+    let mut w = ConstModulo::<M>::new(1);
+    for _ in 0..len {
+        w *= root;
+    }
+    assert_eq!(w, ConstModulo::new(1));
+    if len > 1 {
+        let mut half = ConstModulo::<M>::new(1);
+        for _ in 0..len / 2 {
+            half *= root;
+        }
+        assert_eq!(half, ConstModulo::new(M - 1));
+    }
+
+    let mut root_j = ConstModulo::<M>::new(1);
+    for fa_j in &fa {
+        let mut sum = ConstModulo::<M>::new(0);
+        for x in a.iter().rev() {
+            sum = sum * root_j + *x;
+        }
+        assert_eq!(*fa_j, sum);
+        root_j *= root;
+    }
+}
+
+#[test]
+fn test_forward_naive() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for order in 0..6 {
+        check_forward_naive::<P>(order, &mut rng);
+        // Roots derived from the precomputed ones.
+        check_forward_naive::<{ PRIMES[0] }>(order, &mut rng);
+        check_forward_naive::<{ PRIMES[1] }>(order, &mut rng);
+        check_forward_naive::<{ PRIMES[2] }>(order, &mut rng);
+    }
+}
+
+#[test]
+fn test_forward_inverse() {
+    let mut rng = StdRng::seed_from_u64(2);
+    for order in [0, 1, 2, 5, 10, 12] {
+        let plan = Ntt::<{ PRIMES[0] }>::new(order).unwrap();
+        let a = random_values::<{ PRIMES[0] }>(1 << order, &mut rng);
+        let mut b = a.clone();
+        plan.forward(&mut b);
+        plan.inverse(&mut b);
+        assert_eq!(a, b);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_transform_wrong_length() {
+    let plan = Ntt::<P>::new(3).unwrap();
+    let mut a = vec![ConstModulo::<P>::new(1); 4];
+    plan.forward(&mut a);
+}
+
+#[test]
+fn test_pointwise_mul() {
+    let mut a = [2, 3, P - 1].map(ConstModulo::<P>::new);
+    let b = [5, 7, P - 1].map(ConstModulo::<P>::new);
+    ntt::pointwise_mul(&mut a, &b);
+    assert_eq!(a, [10, 21, 1].map(ConstModulo::<P>::new));
+}
+
+#[test]
+#[should_panic]
+fn test_pointwise_mul_different_lengths() {
+    let mut a = [1, 2].map(ConstModulo::<P>::new);
+    let b = [1, 2, 3].map(ConstModulo::<P>::new);
+    ntt::pointwise_mul(&mut a, &b);
+}
+
+#[test]
+fn test_convolution() {
+    assert_eq!(ntt::convolution::<P>(&[], &[1, 2]), []);
+    assert_eq!(ntt::convolution::<P>(&[3], &[4]), [12]);
+    assert_eq!(
+        ntt::convolution::<P>(&[P + 1, 2 * P], &[u64::MAX]),
+        [u64::MAX % P, 0]
+    );
+
+    let mut rng = StdRng::seed_from_u64(3);
+    for (len_a, len_b) in [(1, 100), (17, 33), (64, 64), (200, 311)] {
+        let a: Vec<u64> = (0..len_a).map(|_| rng.gen()).collect();
+        let b: Vec<u64> = (0..len_b).map(|_| rng.gen()).collect();
+        assert_eq!(ntt::convolution::<P>(&a, &b), naive_convolution(&a, &b, P));
+        assert_eq!(
+            ntt::convolution::<{ PRIMES[1] }>(&a, &b),
+            naive_convolution(&a, &b, PRIMES[1])
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_convolution_too_long() {
+    let _ = ntt::convolution::<65519>(&[1, 2, 3], &[4, 5]);
+}
@@ -312,6 +312,30 @@ fn test_radix_round_trip() {
     );
 }
 
+/// Numbers long enough for formatting and parsing to split work across threads.
+#[cfg(feature = "parallel")]
+#[test]
+fn test_radix_round_trip_parallel() {
+    // Over 500000 bits: the top radix powers are longer than 2000 words on every target.
+    let digits = 160000;
+    let x = ubig!(10).pow(digits);
+    let s = x.to_string();
+    assert_eq!(s.len(), digits + 1);
+    assert!(s.starts_with('1') && s[1..].bytes().all(|c| c == b'0'));
+    assert_eq!(s.parse::<UBig>().unwrap(), x);
+
+    let y = &x - ubig!(1);
+    let s = y.to_string();
+    assert!(s.len() == digits && s.bytes().all(|c| c == b'9'));
+    assert_eq!(s.parse::<UBig>().unwrap(), y);
+
+    let z = ubig!(3).pow(330000) * ubig!(12345) + ubig!(6789);
+    let s = z.in_radix(7).to_string();
+    assert_eq!(UBig::from_str_radix(&s, 7).unwrap(), z);
+    let s = z.to_string();
+    assert_eq!(s.parse::<UBig>().unwrap(), z);
+}
+
 #[test]
 fn test_from_str_radix_with_radix_prefix() {
     assert_eq!(UBig::from_str_with_radix_prefix("17").unwrap(), ubig!(17));
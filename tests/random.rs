@@ -1,4 +1,8 @@
-use ibig::{ibig, ops::DivRem, ubig, UBig};
+use ibig::{
+    ibig,
+    ops::{DivRem, UnsignedAbs},
+    ubig, IBig, UBig,
+};
 use rand::{distributions::uniform::Uniform, prelude::*};
 
 #[test]
@@ -76,3 +80,51 @@ fn test_random_arithmetic() {
         }
     }
 }
+
+#[test]
+fn test_random_gcd() {
+    let mut rng = StdRng::seed_from_u64(4);
+
+    // 10^2 bits: 1000 cases
+    // 10^5 bits: 1 case
+    for log_num_bits in 2..=5 {
+        let num_bits = 10usize.pow(log_num_bits);
+        let num_cases = 10u32.pow(5 - log_num_bits);
+        for _ in 0..num_cases {
+            let len_a = rng.gen_range(10..num_bits);
+            let len_b = rng.gen_range(10..num_bits);
+            let len_c = rng.gen_range(1..num_bits / 2);
+            let c = rng.gen_range(ubig!(1)..ubig!(1) << len_c);
+            let a = rng.gen_range(ubig!(1)..ubig!(1) << len_a) * &c;
+            let b = rng.gen_range(ubig!(1)..ubig!(1) << len_b) * &c;
+
+            let g = a.gcd(&b);
+            assert_eq!(&g % &c, ubig!(0));
+            assert_eq!(&a % &g, ubig!(0));
+            assert_eq!(&b % &g, ubig!(0));
+            let (g2, x, y) = a.extended_gcd(&b);
+            assert_eq!(g2, g);
+            // Any common divisor of a and b divides x * a + y * b, so g is the gcd.
+            assert_eq!(&x * IBig::from(&a) + &y * IBig::from(&b), IBig::from(&g));
+            assert!(x.unsigned_abs() <= b);
+            assert!(y.unsigned_abs() <= a);
+        }
+    }
+}
+
+#[test]
+fn test_random_square() {
+    let mut rng = StdRng::seed_from_u64(4);
+
+    // Cover simple, Karatsuba and Toom-3 squaring.
+    for log_num_bits in 2..=5 {
+        let num_bits = 10usize.pow(log_num_bits);
+        let num_cases = 10u32.pow(6 - log_num_bits);
+        for _ in 0..num_cases {
+            let len = rng.gen_range(10..num_bits);
+            let a = rng.gen_range(ubig!(0)..ubig!(1) << len);
+            let b = a.clone();
+            assert_eq!(a.square(), &a * &b);
+        }
+    }
+}